@@ -36,7 +36,7 @@ fn main() {
     for point in data.iter() {
         ct.insert(*point);
     }
-    println!("{:#?}", ct);
+    println!("{}", ct.to_dot(None));
 
     // // println!("{}\n", ct.tree_string());
     // ct.remove(91.0).ok();
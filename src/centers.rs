@@ -0,0 +1,106 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides farthest-first traversal of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use CoverTree;
+use cluster::PointIndex;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Candidate
+////////////////////////////////////////////////////////////////////////////////
+/// A point waiting to be chosen, ordered by its distance to the nearest chosen
+/// point and then by its position, earliest first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    /// The distance to the nearest chosen point.
+    dist: f64,
+    /// The position of the point.
+    position: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist
+            .partial_cmp(&other.dist)
+            .expect("compare candidate distances")
+            .then_with(|| other.position.cmp(&self.position))
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// farthest_first
+////////////////////////////////////////////////////////////////////////////////
+/// Returns up to `limit` points of the tree in farthest-first order, each with
+/// its distance to the nearest earlier point.
+///
+/// Starting from the root point, each step chooses the point farthest from
+/// those already chosen. Only points within the chosen point's distance can
+/// get closer to the chosen set, so each step is a radius search of the tree
+/// with a shrinking radius.
+pub(crate) fn farthest_first<P>(tree: &CoverTree<P>, limit: usize)
+    -> Vec<(&P, f64)>
+    where P: Point
+{
+    let index = PointIndex::new(tree.iter());
+    let points = index.points();
+    let mut nearest = vec![f64::INFINITY; points.len()];
+    let mut chosen = vec![false; points.len()];
+    let mut candidates = BinaryHeap::new();
+    let mut order = Vec::with_capacity(limit.min(points.len()));
+
+    let mut next = if points.is_empty() {
+        None
+    } else {
+        Some(Candidate { dist: f64::INFINITY, position: 0 })
+    };
+    while let Some(Candidate { dist: radius, position }) = next {
+        if order.len() >= limit { break; }
+        chosen[position] = true;
+        order.push((points[position], radius));
+
+        for (point, dist) in tree.find_within(*points[position], radius) {
+            let i = index.position(point);
+            if !chosen[i] && dist < nearest[i] {
+                nearest[i] = dist;
+                candidates.push(Candidate { dist, position: i });
+            }
+        }
+
+        // Skip candidates already chosen or since moved closer.
+        next = None;
+        while let Some(candidate) = candidates.pop() {
+            let i = candidate.position;
+            if !chosen[i] && candidate.dist == nearest[i] {
+                next = Some(candidate);
+                break;
+            }
+        }
+    }
+    order
+}
@@ -0,0 +1,116 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides DBSCAN clustering.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use CoverTree;
+use super::{Label, PointIndex};
+
+use std::collections::VecDeque;
+
+
+/// Clusters the points of a [`CoverTree`] with DBSCAN.
+///
+/// A point is a core point if at least `min_pts` points, itself included, lie
+/// within `eps` of it. Clusters are the sets of core points reachable from
+/// each other through core points, together with the points within `eps` of
+/// them. Every other point is noise. Neighborhoods are found with
+/// [`CoverTree::find_within`].
+///
+/// Returns each point with its label, in the order of [`CoverTree::iter`].
+/// Cluster ids count up from zero.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+/// [`CoverTree::find_within`]: ../struct.CoverTree.html#method.find_within
+/// [`CoverTree::iter`]: ../struct.CoverTree.html#method.iter
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::cluster::{self, Label};
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 1.2, 5.0, 5.1, 5.2, 9.0];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let labels = cluster::dbscan(&cover_tree, 0.15, 2);
+/// let label_of = |x: f32| labels.iter().find(|&&(p, _)| *p == x).unwrap().1;
+/// assert_eq!(label_of(1.0), label_of(1.2));
+/// assert_ne!(label_of(1.0), label_of(5.0));
+/// assert_eq!(label_of(9.0), Label::Noise);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn dbscan<P>(tree: &CoverTree<P>, eps: f64, min_pts: usize)
+    -> Vec<(&P, Label)>
+    where P: Point
+{
+    let index = PointIndex::new(tree.iter());
+    let points = index.points();
+    let mut labels: Vec<Option<Label>> = vec![None; points.len()];
+    let mut next_cluster = 0;
+
+    for start in 0..points.len() {
+        if labels[start].is_some() { continue; }
+
+        let neighbors = tree.find_within(*points[start], eps);
+        if neighbors.len() < min_pts {
+            // May still be claimed as a border point by a later cluster.
+            labels[start] = Some(Label::Noise);
+            continue;
+        }
+
+        let cluster = Label::Cluster(next_cluster);
+        next_cluster += 1;
+        labels[start] = Some(cluster);
+
+        let mut queue: VecDeque<usize> = neighbors
+            .into_iter()
+            .map(|(point, _)| index.position(point))
+            .collect();
+        while let Some(i) = queue.pop_front() {
+            match labels[i] {
+                Some(Label::Cluster(_)) => continue,
+                Some(Label::Noise) => {
+                    // A border point: join the cluster but do not expand.
+                    labels[i] = Some(cluster);
+                    continue;
+                },
+                None => labels[i] = Some(cluster),
+            }
+
+            let neighbors = tree.find_within(*points[i], eps);
+            if neighbors.len() >= min_pts {
+                queue.extend(neighbors
+                    .into_iter()
+                    .map(|(point, _)| index.position(point)));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(labels)
+        .map(|(&point, label)| (point, label.expect("label every point")))
+        .collect()
+}
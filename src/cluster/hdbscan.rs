@@ -0,0 +1,329 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides HDBSCAN clustering of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use CoverTree;
+use super::{Dendrogram, Label, Merge, PointIndex};
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Member
+////////////////////////////////////////////////////////////////////////////////
+/// A child in the condensed cluster tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Member {
+    /// The point at the given position in [`Hdbscan::labels`].
+    ///
+    /// [`Hdbscan::labels`]: struct.Hdbscan.html#structfield.labels
+    Point(usize),
+    /// The condensed cluster with the given id.
+    Cluster(usize),
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CondensedEdge
+////////////////////////////////////////////////////////////////////////////////
+/// An edge of the condensed cluster tree, recording when a point or cluster
+/// leaves its parent cluster.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CondensedEdge {
+    /// The id of the parent cluster. The root cluster, holding every point,
+    /// has id zero, and every cluster has a larger id than its parent.
+    pub parent: usize,
+    /// The point or cluster leaving the parent.
+    pub child: Member,
+    /// The density at which the child leaves the parent: the reciprocal of
+    /// the mutual reachability distance of the split. Splits at distance zero
+    /// are given the largest density of any other split.
+    pub lambda: f64,
+    /// The number of points in the child.
+    pub size: usize,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Hdbscan
+////////////////////////////////////////////////////////////////////////////////
+/// The result of HDBSCAN clustering, returned by [`hdbscan`].
+///
+/// [`hdbscan`]: fn.hdbscan.html
+#[derive(Debug, Clone)]
+pub struct Hdbscan<'a, P> where P: Point + 'a {
+    /// Each point with its label, in the order of [`CoverTree::iter`].
+    /// Cluster ids count up from zero.
+    ///
+    /// [`CoverTree::iter`]: ../struct.CoverTree.html#method.iter
+    pub labels: Vec<(&'a P, Label)>,
+    /// The strength of each point's membership in its cluster, from zero to
+    /// one, in the same order as the labels. Noise has probability zero.
+    pub probabilities: Vec<f64>,
+    /// The condensed cluster tree, parents before their children.
+    pub condensed_tree: Vec<CondensedEdge>,
+    /// The id in the condensed tree of each cluster in the labels.
+    pub selected: Vec<usize>,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// hdbscan
+////////////////////////////////////////////////////////////////////////////////
+/// Clusters the points of a [`CoverTree`] with HDBSCAN.
+///
+/// The core distance of a point is the distance to its `min_samples`-th
+/// nearest point, itself included. Points are linked through the minimum
+/// spanning tree of the mutual reachability distance, the largest of the
+/// distance between two points and their core distances. Splits of that
+/// hierarchy that leave fewer than `min_cluster_size` points on a side are
+/// treated as points falling out of a cluster, and the most stable of the
+/// remaining clusters are selected. Points outside every selected cluster are
+/// noise.
+///
+/// The core distances are found with [`CoverTree::knn_graph`] and the
+/// spanning tree with dual-tree Borůvka, so any `MetricSpace` works.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+/// [`CoverTree::knn_graph`]: ../struct.CoverTree.html#method.knn_graph
+///
+/// # Panics
+///
+/// Panics if `min_cluster_size` is less than two.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::cluster::{self, Label};
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 1.2, 1.3, 5.0, 5.1, 5.2, 5.3, 20.0];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let result = cluster::hdbscan(&cover_tree, 3, 2);
+/// let label_of = |x: f32| result.labels.iter().find(|&&(p, _)| *p == x).unwrap().1;
+/// assert_eq!(label_of(1.0), label_of(1.3));
+/// assert_eq!(label_of(5.0), label_of(5.3));
+/// assert_ne!(label_of(1.0), label_of(5.0));
+/// assert_eq!(label_of(20.0), Label::Noise);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn hdbscan<P>(tree: &CoverTree<P>, min_cluster_size: usize, min_samples: usize)
+    -> Hdbscan<'_, P>
+    where P: Point
+{
+    assert!(min_cluster_size >= 2, "min_cluster_size must be at least two");
+
+    let index = PointIndex::new(tree.iter());
+    let n = index.points().len();
+
+    // The nearest neighbor lists exclude the point itself.
+    let core: Vec<f64> = match min_samples {
+        0 | 1 => vec![0.0; n],
+        k     => tree.knn_graph(k - 1)
+            .into_iter()
+            .map(|(_, neighbors)| neighbors.last().map_or(0.0, |n| n.1))
+            .collect(),
+    };
+    let edges = tree.spanning_tree(&index, &core);
+    let dendrogram = Dendrogram::new(index.points().to_vec(), edges);
+
+    let (condensed_tree, clusters) = condense(
+        dendrogram.merges(),
+        n,
+        min_cluster_size);
+    let selected = select(&condensed_tree, clusters);
+
+    // The selected cluster holding each condensed cluster, if any. Parents
+    // have smaller ids than their children.
+    let mut parent = vec![0; clusters];
+    for edge in &condensed_tree {
+        if let Member::Cluster(id) = edge.child { parent[id] = edge.parent; }
+    }
+    let mut assigned: Vec<Option<usize>> = vec![None; clusters];
+    for id in 1..clusters {
+        assigned[id] = match selected.binary_search(&id) {
+            Ok(label) => Some(label),
+            Err(_)    => assigned[parent[id]],
+        };
+    }
+
+    // Each point's label and the density at which it leaves its cluster.
+    let mut labels = vec![Label::Noise; n];
+    let mut lambdas = vec![0.0; n];
+    let mut max_lambda = vec![0.0_f64; selected.len()];
+    for edge in &condensed_tree {
+        if let Member::Point(i) = edge.child {
+            if let Some(label) = assigned[edge.parent] {
+                labels[i] = Label::Cluster(label);
+                lambdas[i] = edge.lambda;
+                max_lambda[label] = max_lambda[label].max(edge.lambda);
+            }
+        }
+    }
+    let probabilities = labels.iter()
+        .zip(&lambdas)
+        .map(|(label, &lambda)| match *label {
+            Label::Cluster(c) if lambda >= max_lambda[c] => 1.0,
+            Label::Cluster(c) => lambda / max_lambda[c],
+            Label::Noise      => 0.0,
+        })
+        .collect();
+
+    Hdbscan {
+        labels: index.points().iter().cloned().zip(labels).collect(),
+        probabilities,
+        condensed_tree,
+        selected,
+    }
+}
+
+/// Condenses a single-linkage hierarchy over `n` points, treating splits that
+/// leave fewer than `min_cluster_size` points on a side as points falling out
+/// of the cluster being split. Returns the condensed tree and the number of
+/// clusters in it.
+fn condense(merges: &[Merge], n: usize, min_cluster_size: usize)
+    -> (Vec<CondensedEdge>, usize)
+{
+    let size = |node: usize| if node < n { 1 } else { merges[node - n].size };
+    let mut condensed = Vec::new();
+    if merges.is_empty() {
+        condensed.extend((0..n).map(|i| CondensedEdge {
+            parent: 0,
+            child: Member::Point(i),
+            lambda: f64::INFINITY,
+            size: 1,
+        }));
+        return (condensed, if n > 0 { 1 } else { 0 });
+    }
+
+    // Duplicate points merge at distance zero. Their density is capped at the
+    // largest finite density, so they cannot outweigh every other cluster.
+    let closest = merges.iter()
+        .map(|merge| merge.distance)
+        .filter(|&dist| dist > 0.0)
+        .fold(f64::INFINITY, f64::min);
+
+    let mut next_cluster = 1;
+    let mut stack = vec![(n + merges.len() - 1, 0)];
+    while let Some((node, cluster)) = stack.pop() {
+        let merge = merges[node - n];
+        let lambda = 1.0 / merge.distance.max(closest);
+        let large = |node| size(node) >= min_cluster_size;
+
+        match (large(merge.left), large(merge.right)) {
+            (true, true) => for &child in &[merge.left, merge.right] {
+                condensed.push(CondensedEdge {
+                    parent: cluster,
+                    child: Member::Cluster(next_cluster),
+                    lambda,
+                    size: size(child),
+                });
+                stack.push((child, next_cluster));
+                next_cluster += 1;
+            },
+            (true, false) => {
+                fall_out(merges, n, merge.right, cluster, lambda, &mut condensed);
+                stack.push((merge.left, cluster));
+            },
+            (false, true) => {
+                fall_out(merges, n, merge.left, cluster, lambda, &mut condensed);
+                stack.push((merge.right, cluster));
+            },
+            (false, false) => for &child in &[merge.left, merge.right] {
+                fall_out(merges, n, child, cluster, lambda, &mut condensed);
+            },
+        }
+    }
+    (condensed, next_cluster)
+}
+
+/// Records every point below the given hierarchy node leaving `cluster` at
+/// the given density.
+fn fall_out(
+    merges: &[Merge],
+    n: usize,
+    node: usize,
+    cluster: usize,
+    lambda: f64,
+    condensed: &mut Vec<CondensedEdge>)
+{
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if node < n {
+            condensed.push(CondensedEdge {
+                parent: cluster,
+                child: Member::Point(node),
+                lambda,
+                size: 1,
+            });
+        } else {
+            stack.push(merges[node - n].left);
+            stack.push(merges[node - n].right);
+        }
+    }
+}
+
+/// Returns the ids of the clusters of the condensed tree that maximize the
+/// total stability without nesting, in ascending order. The root cluster is
+/// never selected.
+fn select(condensed: &[CondensedEdge], clusters: usize) -> Vec<usize> {
+    let mut birth = vec![0.0; clusters];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); clusters];
+    for edge in condensed {
+        if let Member::Cluster(id) = edge.child {
+            birth[id] = edge.lambda;
+            children[edge.parent].push(id);
+        }
+    }
+
+    // Stability is the density each point spends in the cluster.
+    let mut stability = vec![0.0; clusters];
+    for edge in condensed {
+        let excess: f64 = edge.lambda - birth[edge.parent];
+        stability[edge.parent] += excess.max(0.0) * edge.size as f64;
+    }
+
+    // Children have larger ids than their parents, so visit them first.
+    let mut chosen = vec![false; clusters];
+    let mut total = stability.clone();
+    for id in (1..clusters).rev() {
+        let below: f64 = children[id].iter().map(|&c| total[c]).sum();
+        if children[id].is_empty() || stability[id] >= below {
+            chosen[id] = true;
+        } else {
+            total[id] = below;
+        }
+    }
+
+    // Keep the highest chosen clusters.
+    let mut covered = vec![false; clusters];
+    let mut selected = Vec::new();
+    for id in 1..clusters {
+        for &child in &children[id] {
+            covered[child] = covered[id] || chosen[id];
+        }
+        if chosen[id] && !covered[id] { selected.push(id); }
+    }
+    selected
+}
@@ -0,0 +1,265 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides k-means++ seeding and k-medoids clustering of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use MetricSpace;
+use Point;
+use CoverTree;
+use super::{Label, PointIndex, SplitMix};
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Center
+////////////////////////////////////////////////////////////////////////////////
+/// A point tagged with its position among the centers, so a tree over the
+/// centers can report which center is nearest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Center<P> {
+    /// The point of the center.
+    point: P,
+    /// The position of the center.
+    id: usize,
+}
+
+impl<P> MetricSpace for Center<P> where P: MetricSpace {
+    fn distance(self, rhs: Self) -> f64 {
+        self.point.distance(rhs.point)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// kmeans_plus_plus
+////////////////////////////////////////////////////////////////////////////////
+/// Chooses `k` points of a [`CoverTree`] as initial centers with k-means++
+/// seeding.
+///
+/// The first center is chosen uniformly at random, and each following center
+/// with probability proportional to the squared distance from a point to its
+/// nearest chosen center. Only points within the largest such distance of a
+/// new center can move closer to the centers, so each step updates them with
+/// a radius search of the tree. The choices are determined by `seed`.
+///
+/// Returns fewer than `k` centers if every remaining point coincides with a
+/// chosen center.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::cluster;
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 5.0, 5.1, 9.0, 9.1];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let centers = cluster::kmeans_plus_plus(&cover_tree, 3, 7);
+/// assert_eq!(centers.len(), 3);
+/// assert_eq!(cluster::kmeans_plus_plus(&cover_tree, 3, 7), centers);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn kmeans_plus_plus<P>(tree: &CoverTree<P>, k: usize, seed: u64) -> Vec<&P>
+    where P: Point
+{
+    let index = PointIndex::new(tree.iter());
+    seed_positions(tree, &index, k, &mut SplitMix::new(seed))
+        .into_iter()
+        .map(|i| index.points()[i])
+        .collect()
+}
+
+/// Returns the positions of up to `k` centers chosen by k-means++ seeding.
+fn seed_positions<P>(
+    tree: &CoverTree<P>,
+    index: &PointIndex<P>,
+    k: usize,
+    rng: &mut SplitMix)
+    -> Vec<usize>
+    where P: Point
+{
+    let points = index.points();
+    let mut centers = Vec::with_capacity(k);
+    if points.is_empty() { return centers; }
+
+    let mut nearest = vec![f64::INFINITY; points.len()];
+    let mut next = rng.below(points.len());
+    while centers.len() < k {
+        centers.push(next);
+        let radius = nearest.iter().cloned().fold(0.0, f64::max);
+        for (point, dist) in tree.find_within(*points[next], radius) {
+            let i = index.position(point);
+            if dist < nearest[i] { nearest[i] = dist; }
+        }
+
+        let total: f64 = nearest.iter().map(|d| d * d).sum();
+        if total <= 0.0 { break; }
+        let mut target = rng.next_f64() * total;
+        next = nearest
+            .iter()
+            .position(|d| {
+                target -= d * d;
+                target < 0.0
+            })
+            .unwrap_or_else(|| nearest.iter().rposition(|&d| d > 0.0)
+                .expect("a point away from the centers"));
+    }
+    centers
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Medoids
+////////////////////////////////////////////////////////////////////////////////
+/// The result of k-medoids clustering, returned by [`k_medoids`].
+///
+/// [`k_medoids`]: fn.k_medoids.html
+#[derive(Debug, Clone)]
+pub struct Medoids<'a, P> where P: Point + 'a {
+    /// The medoid of each cluster, indexed by cluster id.
+    pub medoids: Vec<&'a P>,
+    /// Each point with the cluster of its nearest medoid, in the order of
+    /// [`CoverTree::iter`].
+    ///
+    /// [`CoverTree::iter`]: ../struct.CoverTree.html#method.iter
+    pub labels: Vec<(&'a P, Label)>,
+    /// The sum of the distances from each point to its nearest medoid.
+    pub cost: f64,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// k_medoids
+////////////////////////////////////////////////////////////////////////////////
+/// Clusters the points of a [`CoverTree`] around `k` medoids: points of the
+/// tree minimizing the total distance from each point to its nearest medoid.
+/// Unlike means, medoids exist in any `MetricSpace`.
+///
+/// The medoids are seeded with [`kmeans_plus_plus`] and improved by a
+/// CLARANS-style randomized search: a random medoid is swapped with a random
+/// other point whenever that lowers the cost, until `max_neighbors` swaps in a
+/// row fail. Each swap is scored by assigning every point through a
+/// `CoverTree` over the candidate medoids. The choices are determined by
+/// `seed`.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+/// [`kmeans_plus_plus`]: fn.kmeans_plus_plus.html
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::cluster;
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 1.2, 5.0, 5.1, 5.2];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let result = cluster::k_medoids(&cover_tree, 2, 20, 7);
+/// let mut medoids: Vec<f32> = result.medoids.iter().map(|&&m| m).collect();
+/// medoids.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(medoids, vec![1.1, 5.1]);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn k_medoids<P>(tree: &CoverTree<P>, k: usize, max_neighbors: usize, seed: u64)
+    -> Medoids<'_, P>
+    where P: Point
+{
+    assert!(k > 0, "k_medoids requires at least one medoid");
+
+    let index = PointIndex::new(tree.iter());
+    let points = index.points();
+    let mut rng = SplitMix::new(seed);
+
+    let mut medoids = seed_positions(tree, &index, k, &mut rng);
+    let (mut assignment, mut cost) = assign(points, &medoids);
+
+    let mut failures = 0;
+    while failures < max_neighbors && medoids.len() < points.len() {
+        let replaced = rng.below(medoids.len());
+        let candidate = rng.below(points.len());
+        if medoids.contains(&candidate) {
+            failures += 1;
+            continue;
+        }
+
+        let previous = medoids[replaced];
+        medoids[replaced] = candidate;
+        let (swapped, swapped_cost) = assign(points, &medoids);
+        if swapped_cost < cost {
+            assignment = swapped;
+            cost = swapped_cost;
+            failures = 0;
+        } else {
+            medoids[replaced] = previous;
+            failures += 1;
+        }
+    }
+
+    Medoids {
+        medoids: medoids.iter().map(|&i| points[i]).collect(),
+        labels: points
+            .iter()
+            .cloned()
+            .zip(assignment.into_iter().map(Label::Cluster))
+            .collect(),
+        cost,
+    }
+}
+
+/// Returns the nearest of the medoids at the given positions for each point,
+/// and the total distance from the points to their nearest medoids.
+fn assign<P>(points: &[&P], medoids: &[usize]) -> (Vec<usize>, f64)
+    where P: Point
+{
+    let centers: CoverTree<Center<P>> = medoids
+        .iter()
+        .enumerate()
+        .map(|(id, &i)| Center { point: *points[i], id })
+        .collect();
+
+    let mut cost = 0.0;
+    let assignment = points
+        .iter()
+        .map(|&&point| {
+            let nearest = centers.find_k_nearest(Center { point, id: 0 }, 1);
+            let (center, dist) = nearest[0];
+            cost += dist;
+            center.id
+        })
+        .collect();
+    (assignment, cost)
+}
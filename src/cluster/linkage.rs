@@ -0,0 +1,330 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides the minimum spanning tree of a `CoverTree` and single-linkage
+//! clustering.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+use join::Node;
+use super::{DisjointSet, Label, PointIndex};
+
+use std::collections::HashMap;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// minimum_spanning_tree
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the edges of the minimum spanning tree of the points below the
+/// given `Cover` as pairs of positions in the index, lightest first.
+///
+/// The weight of an edge is the larger of the distance between its points and
+/// their `core` distances, so all zero `core` distances give the usual
+/// metric minimum spanning tree.
+pub(crate) fn minimum_spanning_tree<P>(
+    root: Option<&Cover<P>>,
+    index: &PointIndex<P>,
+    core: &[f64])
+    -> Vec<(usize, usize, f64)>
+    where P: Point
+{
+    let root = match root {
+        Some(root) => root,
+        None       => return Vec::new(),
+    };
+    let n = index.points().len();
+    let mut components = DisjointSet::new(n);
+    let mut edges = Vec::with_capacity(n.saturating_sub(1));
+
+    // Each round links every component to its nearest other component.
+    while edges.len() + 1 < n {
+        let component: Vec<usize> = (0..n).map(|i| components.find(i)).collect();
+        let mut round = Boruvka::new(index, core, component);
+        round.annotate(root);
+        round.visit(Node::Tree(root), Node::Tree(root), 0.0);
+
+        let mut linked = false;
+        for (weight, a, b) in round.best.into_iter().flatten() {
+            if components.union(a, b) {
+                edges.push((a, b, weight));
+                linked = true;
+            }
+        }
+        if !linked { break; }
+    }
+
+    edges.sort_by(|a, b| a.2.partial_cmp(&b.2).expect("sort edges by weight"));
+    edges
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Boruvka
+////////////////////////////////////////////////////////////////////////////////
+/// One round of dual-tree Borůvka, finding the lightest edge from each
+/// component to any other.
+struct Boruvka<'a, 'i, P> where P: Point + 'a, 'a: 'i {
+    /// The positions of the points.
+    index: &'i PointIndex<'a, P>,
+    /// The core distance of each point.
+    core: &'i [f64],
+    /// The component of each point.
+    component: Vec<usize>,
+    /// The component shared by all points below each `Cover`, if there is
+    /// one, keyed by the address of the `Cover`.
+    uniform: HashMap<*const Cover<P>, Option<usize>>,
+    /// The weight of the lightest edge found from each point to another
+    /// component.
+    point_bounds: Vec<f64>,
+    /// A bound on the weight of an edge that could still improve any point
+    /// below each `Cover`, keyed by the address of the `Cover`.
+    subtree_bounds: HashMap<*const Cover<P>, f64>,
+    /// The lightest edge found from each component, by its root position.
+    best: Vec<Option<(f64, usize, usize)>>,
+}
+
+impl<'a, 'i, P> Boruvka<'a, 'i, P> where P: Point {
+    /// Constructs a new round for the given components.
+    fn new(index: &'i PointIndex<'a, P>, core: &'i [f64], component: Vec<usize>)
+        -> Self
+    {
+        let n = component.len();
+        Boruvka {
+            index,
+            core,
+            component,
+            uniform: HashMap::new(),
+            point_bounds: vec![f64::INFINITY; n],
+            subtree_bounds: HashMap::new(),
+            best: vec![None; n],
+        }
+    }
+
+    /// Records the component shared by all points below the given `Cover`
+    /// and each of its descendents.
+    fn annotate(&mut self, cover: &Cover<P>) -> Option<usize> {
+        let mut shared = Some(self.component[self.index.position(cover.point())]);
+        for child in cover.children() {
+            let below = self.annotate(child);
+            if below != shared { shared = None; }
+        }
+        self.uniform.insert(cover as *const _, shared);
+        shared
+    }
+
+    /// Returns the component shared by all points of the set, if there is
+    /// one.
+    fn uniform(&self, node: Node<P>) -> Option<usize> {
+        match node {
+            Node::Point(cover) => {
+                Some(self.component[self.index.position(cover.point())])
+            },
+            Node::Tree(cover) => self.uniform[&(cover as *const _)],
+        }
+    }
+
+    /// Returns the weight of the lightest edge recorded for a component.
+    fn best_weight(&self, component: usize) -> f64 {
+        self.best[component].map_or(f64::INFINITY, |(weight, _, _)| weight)
+    }
+
+    /// Returns the weight an edge must beat to improve any point of the set.
+    fn node_bound(&self, node: Node<P>) -> f64 {
+        let cover = node.cover();
+        match node {
+            Node::Point(_) => {
+                let i = self.index.position(cover.point());
+                self.point_bounds[i].min(self.best_weight(self.component[i]))
+            },
+            Node::Tree(_) => {
+                let stored = self.subtree_bounds
+                    .get(&(cover as *const _))
+                    .cloned()
+                    .unwrap_or(f64::INFINITY);
+                match self.uniform(node) {
+                    Some(component) => stored.min(self.best_weight(component)),
+                    None            => stored,
+                }
+            },
+        }
+    }
+
+    /// Records the edge between the given points if it leaves the component
+    /// of the first and is lighter than those found so far.
+    fn offer(&mut self, a: &P, b: &P, dist: f64) {
+        let (a, b) = (self.index.position(a), self.index.position(b));
+        let component = self.component[a];
+        if component == self.component[b] { return; }
+
+        let weight = dist.max(self.core[a]).max(self.core[b]);
+        if weight < self.point_bounds[a] { self.point_bounds[a] = weight; }
+
+        // Break ties by position so every round picks edges consistently.
+        let edge = (weight, a.min(b), a.max(b));
+        let lighter = match self.best[component] {
+            Some(best) => edge.partial_cmp(&best) == Some(::std::cmp::Ordering::Less),
+            None       => true,
+        };
+        if lighter { self.best[component] = Some(edge); }
+    }
+
+    /// Searches the pairs of a query and reference set whose points are the
+    /// given distance apart.
+    fn visit(&mut self, query: Node<P>, reference: Node<P>, dist: f64) {
+        let q = query.cover();
+        let r = reference.cover();
+        self.offer(q.point(), r.point(), dist);
+
+        // Skip pairs within a single component, and pairs too far apart to
+        // improve any query point.
+        let shared = self.uniform(query);
+        if shared.is_some() && shared == self.uniform(reference) { return; }
+        let lower = dist - query.radius() - reference.radius();
+        if lower > self.node_bound(query) { return; }
+
+        // Split the wider of the sets that can be split.
+        let split_query = match (query.children(), reference.children()) {
+            (None, None)       => return,
+            (Some(_), None)    => true,
+            (None, Some(_))    => false,
+            (Some(_), Some(_)) => query.radius() >= reference.radius(),
+        };
+
+        if split_query {
+            self.visit(Node::Point(q), reference, dist);
+            let mut bound = self.node_bound(Node::Point(q));
+            for child in q.children() {
+                let dist = child.point().distance(*r.point());
+                self.visit(Node::Tree(child), reference, dist);
+                bound = bound.max(self.node_bound(Node::Tree(child)));
+            }
+            self.subtree_bounds.insert(q as *const _, bound);
+        } else {
+            // Visit the reference children nearest first to tighten the
+            // bounds early.
+            let mut children: Vec<(&Cover<P>, f64)> = r.children()
+                .iter()
+                .map(|child| (child, q.point().distance(*child.point())))
+                .collect();
+            children.sort_by(|a, b| a.1
+                .partial_cmp(&b.1)
+                .expect("sort by distance to target"));
+
+            self.visit(query, Node::Point(r), dist);
+            for (child, dist) in children {
+                self.visit(query, Node::Tree(child), dist);
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Merge
+////////////////////////////////////////////////////////////////////////////////
+/// A merge of two clusters in a [`Dendrogram`].
+///
+/// Clusters are numbered as in SciPy's linkage matrices: the points are
+/// clusters `0` to `n - 1`, and the cluster formed by the `i`-th merge is
+/// cluster `n + i`.
+///
+/// [`Dendrogram`]: struct.Dendrogram.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Merge {
+    /// The first merged cluster.
+    pub left: usize,
+    /// The second merged cluster.
+    pub right: usize,
+    /// The distance at which the clusters merge.
+    pub distance: f64,
+    /// The number of points in the merged cluster.
+    pub size: usize,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Dendrogram
+////////////////////////////////////////////////////////////////////////////////
+/// The hierarchy of clusters formed by single-linkage clustering, returned by
+/// [`CoverTree::single_linkage`].
+///
+/// [`CoverTree::single_linkage`]: ../struct.CoverTree.html#method.single_linkage
+#[derive(Debug, Clone)]
+pub struct Dendrogram<'a, P> where P: Point + 'a {
+    /// The clustered points, numbered by their position.
+    points: Vec<&'a P>,
+    /// The minimum spanning tree edges between positions, lightest first.
+    edges: Vec<(usize, usize, f64)>,
+    /// The merges in the order they happen.
+    merges: Vec<Merge>,
+}
+
+impl<'a, P> Dendrogram<'a, P> where P: Point {
+    /// Constructs the `Dendrogram` of the given points from their minimum
+    /// spanning tree edges, lightest first.
+    pub(crate) fn new(points: Vec<&'a P>, edges: Vec<(usize, usize, f64)>)
+        -> Self
+    {
+        let n = points.len();
+        let mut sets = DisjointSet::new(n);
+        let mut cluster_of: Vec<usize> = (0..n).collect();
+        let mut merges = Vec::with_capacity(edges.len());
+
+        for &(a, b, distance) in &edges {
+            let (left, right) = (cluster_of[sets.find(a)], cluster_of[sets.find(b)]);
+            sets.union(a, b);
+            let root = sets.find(a);
+            merges.push(Merge { left, right, distance, size: sets.size_of(root) });
+            cluster_of[root] = n + merges.len() - 1;
+        }
+
+        Dendrogram { points, edges, merges }
+    }
+
+    /// Returns the clustered points, numbered by their position.
+    pub fn points(&self) -> &[&'a P] {
+        &self.points
+    }
+
+    /// Returns the merges in the order they happen, from the closest clusters
+    /// to the farthest.
+    pub fn merges(&self) -> &[Merge] {
+        &self.merges
+    }
+
+    /// Returns each point with its cluster after merging every pair of
+    /// clusters within `threshold` of each other.
+    pub fn cut_at_distance(&self, threshold: f64) -> Vec<(&'a P, Label)> {
+        let merged = self.merges
+            .iter()
+            .take_while(|merge| merge.distance <= threshold)
+            .count();
+        self.labels_after(merged)
+    }
+
+    /// Returns each point with its cluster after merging until `clusters`
+    /// clusters remain, or until every point is in one cluster.
+    pub fn cut_into(&self, clusters: usize) -> Vec<(&'a P, Label)> {
+        self.labels_after(self.points.len().saturating_sub(clusters.max(1)))
+    }
+
+    /// Returns each point with its cluster after the given number of merges.
+    fn labels_after(&self, merged: usize) -> Vec<(&'a P, Label)> {
+        let mut sets = DisjointSet::new(self.points.len());
+        for &(a, b, _) in self.edges.iter().take(merged) {
+            sets.union(a, b);
+        }
+        self.points.iter().cloned().zip(sets.labels()).collect()
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Clustering algorithms answering their neighborhood queries with a
+//! `CoverTree`, so they work for any `MetricSpace`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+// Module declarations.
+mod dbscan;
+mod hdbscan;
+mod kmedoids;
+pub(crate) mod linkage;
+
+// Reexports.
+pub use self::dbscan::dbscan;
+pub use self::hdbscan::{hdbscan, CondensedEdge, Hdbscan, Member};
+pub use self::kmedoids::{k_medoids, kmeans_plus_plus, Medoids};
+pub use self::linkage::{Dendrogram, Merge};
+
+
+use Point;
+
+use std::collections::HashMap;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Label
+////////////////////////////////////////////////////////////////////////////////
+/// The cluster assigned to a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Label {
+    /// The point belongs to the cluster with the given id.
+    Cluster(usize),
+    /// The point belongs to no cluster.
+    Noise,
+}
+
+impl Label {
+    /// Returns the id of the cluster, or `None` for noise.
+    pub fn cluster(&self) -> Option<usize> {
+        match *self {
+            Label::Cluster(id) => Some(id),
+            Label::Noise       => None,
+        }
+    }
+
+    /// Returns `true` if the point belongs to no cluster.
+    pub fn is_noise(&self) -> bool {
+        *self == Label::Noise
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// PointIndex
+////////////////////////////////////////////////////////////////////////////////
+/// Numbers the points of a `CoverTree` in the order of `Iter`, so that the
+/// references returned by queries can be mapped back to their position.
+#[derive(Debug, Clone)]
+pub(crate) struct PointIndex<'a, P> where P: Point + 'a {
+    /// The points in order.
+    points: Vec<&'a P>,
+    /// The position of each point, keyed by its address.
+    positions: HashMap<*const P, usize>,
+}
+
+impl<'a, P> PointIndex<'a, P> where P: Point {
+    /// Constructs a `PointIndex` of the given points.
+    pub(crate) fn new<I>(points: I) -> Self where I: Iterator<Item=&'a P> {
+        let points: Vec<&'a P> = points.collect();
+        let positions = points
+            .iter()
+            .enumerate()
+            .map(|(i, &point)| (point as *const P, i))
+            .collect();
+        PointIndex { points, positions }
+    }
+
+    /// Returns the points in order.
+    pub(crate) fn points(&self) -> &[&'a P] {
+        &self.points
+    }
+
+    /// Returns the position of the given point, which must be one of the
+    /// indexed points.
+    pub(crate) fn position(&self, point: &P) -> usize {
+        self.positions[&(point as *const P)]
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DisjointSet
+////////////////////////////////////////////////////////////////////////////////
+/// A union-find structure over the positions `0..n`.
+#[derive(Debug, Clone)]
+pub(crate) struct DisjointSet {
+    /// The parent of each position. Roots are their own parent.
+    parent: Vec<usize>,
+    /// The number of positions in the set of each root.
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    /// Constructs a `DisjointSet` with each of `n` positions in its own set.
+    pub(crate) fn new(n: usize) -> Self {
+        DisjointSet { parent: (0..n).collect(), size: vec![1; n] }
+    }
+
+    /// Returns the root of the set holding the given position.
+    pub(crate) fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            // Path halving.
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Returns the number of positions in the set holding the given position.
+    pub(crate) fn size_of(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+
+    /// Merges the sets holding the given positions. Returns `false` if they
+    /// were already in the same set.
+    pub(crate) fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b { return false; }
+        if self.size[a] < self.size[b] { ::std::mem::swap(&mut a, &mut b); }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+
+    /// Returns a label for each position, numbering the sets from zero in
+    /// the order their first positions appear.
+    pub(crate) fn labels(&mut self) -> Vec<Label> {
+        let mut ids: HashMap<usize, usize> = HashMap::new();
+        (0..self.parent.len())
+            .map(|i| {
+                let root = self.find(i);
+                let next = ids.len();
+                Label::Cluster(*ids.entry(root).or_insert(next))
+            })
+            .collect()
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// SplitMix
+////////////////////////////////////////////////////////////////////////////////
+/// A small seeded pseudorandom generator (SplitMix64), so randomized
+/// algorithms are reproducible without extra dependencies.
+#[derive(Debug, Clone)]
+pub(crate) struct SplitMix {
+    /// The generator state.
+    state: u64,
+}
+
+impl SplitMix {
+    /// Constructs a `SplitMix` generator from the given seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        SplitMix { state: seed }
+    }
+
+    /// Returns the next pseudorandom 64-bit value.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudorandom value in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a pseudorandom value in `0..n`. The value `n` must not be zero.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
@@ -10,6 +10,7 @@
 
 use std::mem;
 use Point;
+use iter::Iter;
 
 /// A node in a `CoverTree` containing a point of type P.
 #[derive(Debug, Clone, PartialEq)]
@@ -21,8 +22,13 @@ pub struct Cover<P> where P: Point {
     children: Vec<Cover<P>>,
     /// The level of the `Cover`.
     level: usize,
-    /// The maximum distance from the point to any of its descendents.
-    max_distance: Option<f64>
+    /// The maximum distance from the point to any of its descendents. Kept up
+    /// to date by insertion and removal.
+    max_distance: Option<f64>,
+    /// The number of points in the `Cover` and its descendents.
+    size: usize,
+    /// The number of edges on the longest path down to a descendent.
+    height: usize,
 }
 
 
@@ -33,12 +39,69 @@ impl<P> Cover<P> where P: Point {
             point,
             children: Vec::new(),
             level,
-            max_distance: None
+            max_distance: Some(0.0),
+            size: 1,
+            height: 0,
         }
     }
 
+    /// Returns the point stored in the `Cover`.
+    pub(crate) fn point(&self) -> &P {
+        &self.point
+    }
+
+    /// Returns the children of the `Cover`.
+    pub(crate) fn children(&self) -> &[Cover<P>] {
+        &self.children
+    }
+
+    /// Returns the level of the `Cover`.
+    pub(crate) fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the number of points in the `Cover` and its descendents.
+    pub(crate) fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of edges on the longest path from the `Cover` down
+    /// to one of its descendents.
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the point at the given position in a pre-order walk of the
+    /// `Cover` and its descendents, descending only through the `Cover`s
+    /// holding it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the size of the `Cover`.
+    pub(crate) fn nth_point(&self, mut index: usize) -> &P {
+        let mut cover = self;
+        while index > 0 {
+            index -= 1;
+            let mut next = None;
+            for child in &cover.children {
+                if index < child.size {
+                    next = Some(child);
+                    break;
+                }
+                index -= child.size;
+            }
+            cover = next.expect("index within the Cover");
+        }
+        &cover.point
+    }
+
+    /// Consumes the `Cover`, returning its point and children.
+    pub(crate) fn into_parts(self) -> (P, Vec<Cover<P>>) {
+        (self.point, self.children)
+    }
+
     /// Returns the size of the cover.
-    fn cover_distance(&self, span_factor: f64) -> f64 {
+    pub(crate) fn cover_distance(&self, span_factor: f64) -> f64 {
         span_factor.powf(self.level as f64)
     }
 
@@ -46,9 +109,9 @@ impl<P> Cover<P> where P: Point {
     /// its children's points. Ignores any cached value.
     fn max_distance_(&self) -> f64 {
         let mut dist = 0.0;
-        for descendent in self.descendents() {
+        for descendent in Iter::new(&self.children, 0) {
             dist = self.point
-                .distance(descendent.point)
+                .distance(*descendent)
                 .max(dist);
         }
         dist
@@ -56,74 +119,40 @@ impl<P> Cover<P> where P: Point {
 
     /// Returns the maximum distance between the `Cover`s point and any of its
     /// children's points.
-    fn max_distance(&mut self) -> f64 {
-        if let Some(dist) = self.max_distance {
-            dist
-        } else {
-            let dist = self.max_distance_();
-            self.max_distance = Some(dist);
-            dist
-        }
+    pub(crate) fn max_distance(&self) -> f64 {
+        self.max_distance.unwrap_or_else(|| self.max_distance_())
     }
 
-    /// Returns all of the `Cover`s under this one.
-    fn descendents(&self) -> Vec<&Cover<P>> {
-        let mut descendents: Vec<&Cover<P>> = Vec::new();
-
-        for child in &self.children {
-            descendents.push(&child);
-            for desc in child.descendents() {
-                descendents.push(desc);
-            }
-        }
-        descendents
+    /// Recalculates the height after the children of the `Cover` have
+    /// changed.
+    fn refresh_height(&mut self) {
+        self.height = self.children
+            .iter()
+            .map(|child| child.height + 1)
+            .max()
+            .unwrap_or(0);
     }
 
-    /// Adds a new child `Cover` to this node.
-    fn add_child(&mut self, cover: Cover<P>) {
-        self.children.push(cover);
+    /// Recalculates the cached maximum distance after the descendents of the
+    /// `Cover` have changed.
+    fn refresh_max_distance(&mut self) {
+        self.max_distance = Some(self.max_distance_());
     }
 
-    // Pseudocode from paper:
-    // function findNearestNeighbor(Point tree p,
-    //                              query point x,
-    //                              nearest neighbor so far y)
-    //     if P(p, x) < P(y, x) then
-    //         y←p
-    //     for each child q of p sorted by distance to x do
-    //         if P(y, x) > P(x, q) − maxdist(q) then
-    //             y ← findNearestNeighbor(q, x, y)
-    //     return y
-    pub fn find_nearest<'a>(&'a mut self,
-                            query: P,
-                            nearest_yet: Option<&'a P>)
-                            -> &'a P {
-
-        // Save closes value yet seen.
-        let mut nearest = if nearest_yet.is_none() || self.point.distance(query) < nearest_yet
-            .expect("point is nearest yet")
-            .distance(query)
-        {
-            &self.point
-        } else {
-            nearest_yet.expect("provided is nearest yet")
-        };
+    /// Returns `true` if the given point is stored in this `Cover` or any of
+    /// its descendents.
+    pub(crate) fn contains(&self, query: &P) -> bool {
+        if self.point == *query { return true; }
 
-        // Sort children by distance to query point.
-        self.children.sort_by(|a: &Cover<P>, b: &Cover<P>|
-            a.point
-                .distance(query)
-                .partial_cmp(&b.point.distance(query))
-                .expect("sort by distance to target")
-        );
+        // A descendent equal to the query must lie within max_distance.
+        self.children.iter().any(|child|
+            child.point.distance(*query) <= child.max_distance() &&
+            child.contains(query))
+    }
 
-        for child in &mut self.children {
-            // If closer points could be below this one, recurse.
-            if nearest.distance(query) > query.distance(child.point) - child.max_distance() {
-                nearest = child.find_nearest(query, Some(&nearest));
-            }
-        }
-        nearest
+    /// Adds a new child `Cover` to this node.
+    fn add_child(&mut self, cover: Cover<P>) {
+        self.children.push(cover);
     }
 
     // Pseudocode from paper:
@@ -139,15 +168,15 @@ impl<P> Cover<P> where P: Point {
               point: P,
               span_factor: f64) ->Cover<P> {
 
-        // Cache the maximum distance for this Cover.
-        self.max_distance.map(|x| x.max(self.point.distance(point)));
-
         if self.point.distance(point) > self.cover_distance(span_factor) {
             while self.point.distance(point) > self.cover_distance(span_factor) * 2.0 {
                 self.promote_leaf();
             }
             let mut root = Cover::new(point, self.level + 1);
+            root.size += self.size;
+            root.height = self.height + 1;
             root.children = vec![self];
+            root.refresh_max_distance();
             return root;
         }
 
@@ -174,7 +203,8 @@ impl<P> Cover<P> where P: Point {
                 "CoverTree invariant violated: P(p,x) ≤ covdist(p)");
 
         // Cache the maximum distance for this Cover.
-        self.max_distance.map(|x| x.max(self.point.distance(point)));
+        self.max_distance = self.max_distance.map(|x| x.max(dist));
+        self.size += 1;
 
         let mut done = false;
 
@@ -187,7 +217,7 @@ impl<P> Cover<P> where P: Point {
                     .insert_(point, span_factor);
 
                 // Restore child to where it was.
-                mem::replace(child, child_new);
+                *child = child_new;
 
                 // We want to return self, but we've borrowed children,
                 // so we just set a flag and break instead.
@@ -202,10 +232,13 @@ impl<P> Cover<P> where P: Point {
             let new_cover = Cover::new(point, self.level-1);
             self.add_child(new_cover);
         }
+        self.refresh_height();
         self
     }
 
-    pub fn remove(&mut self, query: P) -> Option<P> {
+    /// Removes the descendent `Cover` holding the given point and returns it
+    /// along with its own descendents, which the caller must reinsert.
+    pub(crate) fn remove(&mut self, query: P) -> Option<Cover<P>> {
         let mut removed = None;
         let mut was_last = false;
 
@@ -221,17 +254,24 @@ impl<P> Cover<P> where P: Point {
             .iter()
             .position(|x| x.point == query) {
             // Remove leaf and set was_last flag if needed.
-            removed = Some(self.children.swap_remove(index).point);
-            if self.children.len() == 0 {was_last = true;}
+            removed = Some(self.children.swap_remove(index));
+            if self.children.is_empty() {was_last = true;}
         } else {
             for child in &mut self.children {
+                // The point can only be below children within reach of it.
+                if child.point.distance(query) > child.max_distance() {continue;}
                 removed = child.remove(query);
                 if removed.is_some() {break;}
             }
         }
 
         if was_last {self.children = Vec::new();} // Erase empty Vec.
-        if removed.is_some() {self.max_distance = None;} // Clear cache.
+        if let Some(ref cover) = removed {
+            // Update caches.
+            self.size -= cover.size;
+            self.refresh_height();
+            self.refresh_max_distance();
+        }
         removed
     }
 
@@ -244,13 +284,16 @@ impl<P> Cover<P> where P: Point {
         } else if let Some(leaf) = self.remove_leaf() {
             let old_root = mem::replace(self, leaf);
             self.level = old_root.level + 1;
+            self.size += old_root.size;
+            self.height = old_root.height + 1;
             self.add_child(old_root);
+            self.refresh_max_distance();
         }
     }
 
     fn remove_leaf(&mut self) -> Option<Cover<P>> {
         // Find index of leaf.
-        if let Some(index) = self.children
+        let leaf = if let Some(index) = self.children
             .iter()
             .position(|x| x.children.is_empty())
         {
@@ -261,8 +304,14 @@ impl<P> Cover<P> where P: Point {
             self.children
                 .first_mut()
                 .expect("get first child")
-                .remove_leaf();
-            None
+                .remove_leaf()
+        };
+        if leaf.is_some() {
+            // Update caches.
+            self.size -= 1;
+            self.refresh_height();
+            self.refresh_max_distance();
         }
+        leaf
     }
 }
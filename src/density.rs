@@ -0,0 +1,103 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides kernel density estimation over a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Kernel
+////////////////////////////////////////////////////////////////////////////////
+/// A kernel for [`CoverTree::kernel_density`], weighting each point by its
+/// distance to the query scaled by the bandwidth.
+///
+/// Kernels are unnormalized, peaking at one for a point at the query, since
+/// the normalizing constant depends on the dimension of the space.
+///
+/// [`CoverTree::kernel_density`]: struct.CoverTree.html#method.kernel_density
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kernel {
+    /// `exp(-u² / 2)` for a scaled distance `u`.
+    Gaussian,
+    /// `1 - u²` for a scaled distance `u` below one, and zero beyond.
+    Epanechnikov,
+    /// One for a scaled distance of at most one, and zero beyond.
+    TopHat,
+}
+
+impl Kernel {
+    /// Returns the weight of a point at the given distance from the query.
+    /// Weights never increase with distance.
+    pub fn weight(self, dist: f64, bandwidth: f64) -> f64 {
+        let u = dist / bandwidth;
+        match self {
+            Kernel::Gaussian     => (-0.5 * u * u).exp(),
+            Kernel::Epanechnikov => (1.0 - u * u).max(0.0),
+            Kernel::TopHat       => if u <= 1.0 { 1.0 } else { 0.0 },
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// kernel_density
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the mean kernel weight of the points below the given root for the
+/// query, within `tolerance` of the exact value.
+///
+/// A subtree within `max_distance` of its point has weights between those at
+/// the nearest and farthest distance it could reach. When those bounds are
+/// within twice the tolerance, the subtree is counted at their midpoint
+/// without measuring the distance to its other points.
+pub(crate) fn kernel_density<P>(
+    root: Option<&Cover<P>>,
+    query: P,
+    kernel: Kernel,
+    bandwidth: f64,
+    tolerance: f64)
+    -> f64
+    where P: Point
+{
+    assert!(bandwidth > 0.0, "kernel bandwidth must be positive");
+
+    let mut total = 0.0;
+    let mut count = 0;
+    let mut stack: Vec<(&Cover<P>, f64)> = root
+        .into_iter()
+        .map(|cover| (cover, cover.point().distance(query)))
+        .collect();
+    while let Some((cover, dist)) = stack.pop() {
+        if !cover.children().is_empty() {
+            let radius = cover.max_distance();
+            let high = kernel.weight((dist - radius).max(0.0), bandwidth);
+            let low = kernel.weight(dist + radius, bandwidth);
+            if high - low <= 2.0 * tolerance {
+                let size = cover.size();
+                total += (high + low) / 2.0 * size as f64;
+                count += size;
+                continue;
+            }
+        }
+
+        total += kernel.weight(dist, bandwidth);
+        count += 1;
+        for child in cover.children() {
+            stack.push((child, child.point().distance(query)));
+        }
+    }
+
+    if count == 0 { 0.0 } else { total / count as f64 }
+}
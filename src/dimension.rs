@@ -0,0 +1,77 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides intrinsic dimension estimates of the points of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use node::Levels;
+
+
+/// Returns the maximum likelihood estimate of the intrinsic dimension from
+/// the nearest neighbor distances of each point, nearest first.
+///
+/// Each point's estimate is the reciprocal of the mean of `ln(T_k / T_j)`
+/// over its nearer neighbor distances `T_j`, following Levina and Bickel.
+/// The estimates are combined by averaging their reciprocals, following
+/// MacKay and Ghahramani. Points with fewer than two neighbors, or with a
+/// neighbor at distance zero, are skipped.
+pub(crate) fn mle<I>(neighbor_distances: I) -> Option<f64>
+    where I: Iterator<Item=Vec<f64>>
+{
+    let mut total = 0.0;
+    let mut count = 0;
+    for distances in neighbor_distances {
+        if distances.len() < 2 || distances[0] <= 0.0 { continue; }
+        let farthest = distances[distances.len() - 1];
+        let inverse = distances[..distances.len() - 1]
+            .iter()
+            .map(|dist| (farthest / dist).ln())
+            .sum::<f64>() / (distances.len() - 1) as f64;
+        total += inverse;
+        count += 1;
+    }
+
+    if count == 0 || total <= 0.0 { return None; }
+    Some(count as f64 / total)
+}
+
+/// Returns an estimate of the intrinsic dimension from the growth of the
+/// number of points at or above each level of the tree.
+///
+/// The points at or above a level form a net at the level's cover distance,
+/// and the size of such a net grows like the cover distance to the power of
+/// minus the dimension. The estimate is the least squares slope of the
+/// logarithm of the net size against the logarithm of the cover distance.
+pub(crate) fn level_growth<P>(levels: Levels<P>, span_factor: f64)
+    -> Option<f64>
+    where P: Point
+{
+    let mut samples = Vec::new();
+    let mut net_size = 0;
+    for (level, nodes) in levels {
+        net_size += nodes.len();
+        samples.push((level as f64 * span_factor.ln(), (net_size as f64).ln()));
+    }
+    if samples.len() < 2 { return None; }
+
+    let n = samples.len() as f64;
+    let mean_x = samples.iter().map(|s| s.0).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|s| s.1).sum::<f64>() / n;
+    let covariance: f64 = samples
+        .iter()
+        .map(|&(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = samples.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    Some(-covariance / variance)
+}
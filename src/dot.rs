@@ -0,0 +1,71 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides Graphviz DOT export of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use node::CoverNode;
+
+use std::fmt::Debug;
+use std::fmt::Write;
+
+
+/// Renders the tree below the given root in the Graphviz DOT language. Nodes
+/// deeper than `max_depth` are left out, and their parents are marked with the
+/// number of hidden children.
+pub(crate) fn to_dot<P>(root: Option<CoverNode<P>>, max_depth: Option<usize>)
+    -> String
+    where P: Point + Debug
+{
+    let mut dot = String::from("digraph CoverTree {\n    node [shape=box];\n");
+
+    // Each stack entry holds a node, its id and the id of its parent.
+    let mut stack: Vec<(CoverNode<P>, usize, Option<usize>)> = root
+        .into_iter()
+        .map(|node| (node, 0, None))
+        .collect();
+    let mut next_id = 1;
+
+    while let Some((node, id, parent)) = stack.pop() {
+        let hidden = if max_depth.is_some_and(|max| node.depth() >= max) {
+            node.children().len()
+        } else {
+            for child in node.children() {
+                stack.push((child, next_id, Some(id)));
+                next_id += 1;
+            }
+            0
+        };
+
+        let mut label = format!("{}\\nlevel {}\\nr = {}",
+            escape(&format!("{:?}", node.point())),
+            node.level(),
+            node.cover_distance());
+        if hidden > 0 {
+            let _ = write!(label, "\\n(+{} hidden)", hidden);
+        }
+        let _ = writeln!(dot, "    n{} [label=\"{}\"];", id, label);
+        if let Some(parent) = parent {
+            let _ = writeln!(dot, "    n{} -> n{};", parent, id);
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Escapes backslashes and double quotes for use in a DOT label.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,136 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides iterators over the points of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+
+use std::slice;
+use std::vec;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Iter
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over references to the [`Point`]s of a [`CoverTree`].
+///
+/// Points are yielded in depth-first order. The iterator keeps one slice
+/// iterator per level of the tree on a stack, so no allocation is done after
+/// the stack has grown to the depth of the tree.
+///
+/// [`Point`]: trait.Point.html
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug, Clone)]
+pub struct Iter<'a, P> where P: Point + 'a {
+    /// The children of each `Cover` on the path to the current one.
+    stack: Vec<slice::Iter<'a, Cover<P>>>,
+    /// The number of points left to yield.
+    remaining: usize,
+}
+
+impl<'a, P> Iter<'a, P> where P: Point {
+    /// Constructs an `Iter` over the given `Cover`s and all of their
+    /// descendents.
+    pub(crate) fn new(covers: &'a [Cover<P>], remaining: usize) -> Self {
+        Iter {
+            stack: vec![covers.iter()],
+            remaining,
+        }
+    }
+}
+
+impl<'a, P> Iterator for Iter<'a, P> where P: Point {
+    type Item = &'a P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = self.stack.last_mut()?.next();
+            match next {
+                Some(cover) => {
+                    if !cover.children().is_empty() {
+                        self.stack.push(cover.children().iter());
+                    }
+                    self.remaining = self.remaining.saturating_sub(1);
+                    return Some(cover.point());
+                },
+                None => { self.stack.pop(); },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, P> ExactSizeIterator for Iter<'a, P> where P: Point {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// IntoIter
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over the [`Point`]s of a [`CoverTree`], taking ownership of
+/// them.
+///
+/// Points are yielded in depth-first order, and each `Cover` is dismantled as
+/// its point is yielded.
+///
+/// [`Point`]: trait.Point.html
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug)]
+pub struct IntoIter<P> where P: Point {
+    /// The remaining children of each `Cover` on the path to the current one.
+    stack: Vec<vec::IntoIter<Cover<P>>>,
+    /// The number of points left to yield.
+    remaining: usize,
+}
+
+impl<P> IntoIter<P> where P: Point {
+    /// Constructs an `IntoIter` over the given `Cover`s and all of their
+    /// descendents.
+    pub(crate) fn new(covers: Vec<Cover<P>>, remaining: usize) -> Self {
+        IntoIter {
+            stack: vec![covers.into_iter()],
+            remaining,
+        }
+    }
+}
+
+impl<P> Iterator for IntoIter<P> where P: Point {
+    type Item = P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = self.stack.last_mut()?.next();
+            match next {
+                Some(cover) => {
+                    let (point, children) = cover.into_parts();
+                    if !children.is_empty() {
+                        self.stack.push(children.into_iter());
+                    }
+                    self.remaining = self.remaining.saturating_sub(1);
+                    return Some(point);
+                },
+                None => { self.stack.pop(); },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<P> ExactSizeIterator for IntoIter<P> where P: Point {}
@@ -0,0 +1,437 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides dual-tree traversals pairing up the `Cover`s of one or two trees.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+
+use iter::Iter;
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::ptr;
+use std::slice;
+use std::thread;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Node
+////////////////////////////////////////////////////////////////////////////////
+/// A set of points taken from a `Cover`: either its point alone or its point
+/// and all of its descendents.
+#[derive(Debug)]
+pub(crate) enum Node<'a, P> where P: Point + 'a {
+    /// The point of the `Cover` alone.
+    Point(&'a Cover<P>),
+    /// The point of the `Cover` and all of its descendents.
+    Tree(&'a Cover<P>),
+}
+
+impl<'a, P> Clone for Node<'a, P> where P: Point {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, P> Copy for Node<'a, P> where P: Point {}
+
+impl<'a, P> Node<'a, P> where P: Point {
+    /// Returns the `Cover` the set is taken from.
+    pub(crate) fn cover(self) -> &'a Cover<P> {
+        match self {
+            Node::Point(cover) | Node::Tree(cover) => cover,
+        }
+    }
+
+    /// Returns the maximum distance from the `Cover`s point to any point of
+    /// the set.
+    pub(crate) fn radius(self) -> f64 {
+        match self {
+            Node::Point(_)    => 0.0,
+            Node::Tree(cover) => cover.max_distance(),
+        }
+    }
+
+    /// Returns the children the set can be split into besides its point, if
+    /// it holds more than one point.
+    pub(crate) fn children(self) -> Option<&'a [Cover<P>]> {
+        match self {
+            Node::Tree(cover) if !cover.children().is_empty() => {
+                Some(cover.children())
+            },
+            _ => None,
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// self_join
+////////////////////////////////////////////////////////////////////////////////
+/// Returns every pair of distinct `Cover`s below the given one whose points
+/// lie within `radius` of each other. Each pair is reported once.
+pub(crate) fn self_join<P>(root: Option<&Cover<P>>, radius: f64)
+    -> Vec<(&P, &P, f64)>
+    where P: Point
+{
+    let mut pairs = Vec::new();
+    if let Some(root) = root {
+        join_within_self(root, radius, &mut pairs);
+    }
+    pairs
+}
+
+/// Collects the close pairs among the `Cover` and its descendents.
+fn join_within_self<'a, P>(
+    cover: &'a Cover<P>,
+    radius: f64,
+    pairs: &mut Vec<(&'a P, &'a P, f64)>)
+    where P: Point
+{
+    let children = cover.children();
+    for (i, child) in children.iter().enumerate() {
+        let dist = cover.point().distance(*child.point());
+        join_across(Node::Point(cover), Node::Tree(child), dist, radius, pairs);
+        join_within_self(child, radius, pairs);
+        for other in &children[i + 1..] {
+            let dist = child.point().distance(*other.point());
+            join_across(Node::Tree(child), Node::Tree(other), dist, radius, pairs);
+        }
+    }
+}
+
+/// Collects the close pairs with one point from each of two disjoint sets
+/// whose points are the given distance apart.
+fn join_across<'a, 'b, P>(
+    a: Node<'a, P>,
+    b: Node<'b, P>,
+    dist: f64,
+    radius: f64,
+    pairs: &mut Vec<(&'a P, &'b P, f64)>)
+    where P: Point
+{
+    // No pair can be close if the sets are too far apart.
+    if dist - a.radius() - b.radius() > radius { return; }
+
+    // Split the wider of the sets that can be split.
+    let split_a = match (a.children(), b.children()) {
+        (None, None) => {
+            if dist <= radius {
+                pairs.push((a.cover().point(), b.cover().point(), dist));
+            }
+            return;
+        },
+        (Some(_), None) => true,
+        (None, Some(_)) => false,
+        (Some(_), Some(_)) => a.radius() >= b.radius(),
+    };
+
+    if split_a {
+        join_across(Node::Point(a.cover()), b, dist, radius, pairs);
+        for child in a.cover().children() {
+            let dist = child.point().distance(*b.cover().point());
+            join_across(Node::Tree(child), b, dist, radius, pairs);
+        }
+    } else {
+        join_across(a, Node::Point(b.cover()), dist, radius, pairs);
+        for child in b.cover().children() {
+            let dist = a.cover().point().distance(*child.point());
+            join_across(a, Node::Tree(child), dist, radius, pairs);
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// join_within
+////////////////////////////////////////////////////////////////////////////////
+/// Returns every pair of a point below the first `Cover` and a point below
+/// the second one lying within `radius` of each other.
+pub(crate) fn join_within<'a, 'b, P>(
+    a: Option<&'a Cover<P>>,
+    b: Option<&'b Cover<P>>,
+    radius: f64)
+    -> Vec<(&'a P, &'b P, f64)>
+    where P: Point
+{
+    let mut pairs = Vec::new();
+    if let (Some(a), Some(b)) = (a, b) {
+        let dist = a.point().distance(*b.point());
+        join_across(Node::Tree(a), Node::Tree(b), dist, radius, &mut pairs);
+    }
+    pairs
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DualSearch
+////////////////////////////////////////////////////////////////////////////////
+/// A search for the `k` nearest reference points of every query point,
+/// traversing the query and reference trees together.
+pub(crate) struct DualSearch<'q, 'r, P> where P: Point + 'q + 'r {
+    /// The number of neighbors to find for each query point.
+    k: usize,
+    /// Whether a query point is kept from being its own neighbor, for
+    /// searching a tree against itself.
+    exclude_self: bool,
+    /// The nearest reference points found so far for each query `Cover`,
+    /// nearest first, keyed by the address of the `Cover`.
+    found: HashMap<*const Cover<P>, Vec<(&'r P, f64)>>,
+    /// A bound on the distance to the k-th neighbor of every query point
+    /// below each query `Cover`, keyed by the address of the `Cover`.
+    subtree_bounds: HashMap<*const Cover<P>, f64>,
+    /// The query `Cover`s in the order they were first reached.
+    queries: Vec<&'q Cover<P>>,
+}
+
+impl<'q, 'r, P> DualSearch<'q, 'r, P> where P: Point {
+    /// Constructs a new `DualSearch` for the `k` nearest neighbors.
+    pub(crate) fn new(k: usize) -> Self {
+        DualSearch {
+            k,
+            exclude_self: false,
+            found: HashMap::new(),
+            subtree_bounds: HashMap::new(),
+            queries: Vec::new(),
+        }
+    }
+
+    /// Keeps each query point from being found as its own neighbor.
+    pub(crate) fn excluding_self(mut self) -> Self {
+        self.exclude_self = true;
+        self
+    }
+
+    /// Runs the search for the query `Cover` and its descendents against the
+    /// reference `Cover` and its descendents. Returns each query point with
+    /// its nearest reference points.
+    pub(crate) fn run(mut self,
+                      query: Option<&'q Cover<P>>,
+                      reference: Option<&'r Cover<P>>)
+        -> Vec<(&'q P, Vec<(&'r P, f64)>)>
+    {
+        if let (Some(query), Some(reference)) = (query, reference) {
+            self.search(Node::Tree(query), reference);
+        }
+        self.finish()
+    }
+
+    /// Searches for the neighbors of the points in the query set below the
+    /// reference `Cover`.
+    fn search(&mut self, query: Node<'q, P>, reference: &'r Cover<P>) {
+        if self.k == 0 { return; }
+        let dist = query.cover().point().distance(*reference.point());
+        self.visit(query, Node::Tree(reference), dist);
+    }
+
+    /// Returns each query point searched for with its nearest reference
+    /// points, in the order the query points were first reached.
+    fn finish(self) -> Vec<(&'q P, Vec<(&'r P, f64)>)> {
+        let mut found = self.found;
+        self.queries
+            .into_iter()
+            .map(|cover| {
+                let neighbors = found
+                    .remove(&(cover as *const _))
+                    .unwrap_or_default();
+                (cover.point(), neighbors)
+            })
+            .collect()
+    }
+
+    /// Returns the distance a reference point must beat to be among the
+    /// neighbors of the given query `Cover`.
+    fn bound(&self, query: &Cover<P>) -> f64 {
+        match self.found.get(&(query as *const _)) {
+            Some(neighbors) if neighbors.len() >= self.k => {
+                neighbors[self.k - 1].1
+            },
+            _ => f64::INFINITY,
+        }
+    }
+
+    /// Returns the distance a reference point must beat to be among the
+    /// neighbors of any point in the given query set.
+    fn node_bound(&self, query: Node<P>) -> f64 {
+        let cover = query.cover();
+        match query {
+            Node::Point(_) => self.bound(cover),
+            Node::Tree(_)  => {
+                // Every query point below the cover has k candidates within
+                // the bound of the cover plus its distance to the cover.
+                let inherited = self.bound(cover) + cover.max_distance();
+                self.subtree_bounds
+                    .get(&(cover as *const _))
+                    .map_or(inherited, |&bound| bound.min(inherited))
+            },
+        }
+    }
+
+    /// Records the reference point as a neighbor of the query `Cover` if it
+    /// is among the nearest found so far.
+    fn offer(&mut self, query: &'q Cover<P>, point: &'r P, dist: f64) {
+        if self.exclude_self && ptr::eq(query.point(), point) { return; }
+        let k = self.k;
+        let queries = &mut self.queries;
+        let neighbors = self.found
+            .entry(query as *const _)
+            .or_insert_with(|| {
+                queries.push(query);
+                Vec::with_capacity(k)
+            });
+
+        if neighbors.len() >= k && dist >= neighbors[k - 1].1 { return; }
+        if neighbors.iter().any(|&(p, _)| ptr::eq(p, point)) { return; }
+        let index = neighbors
+            .iter()
+            .position(|&(_, d)| dist < d)
+            .unwrap_or(neighbors.len());
+        neighbors.insert(index, (point, dist));
+        neighbors.truncate(k);
+    }
+
+    /// Searches the pairs of a query and reference set whose points are the
+    /// given distance apart.
+    fn visit(&mut self, query: Node<'q, P>, reference: Node<'r, P>, dist: f64) {
+        let q = query.cover();
+        let r = reference.cover();
+        self.offer(q, r.point(), dist);
+
+        // Skip the pair if no reference point can come closer to any query
+        // point than its current neighbors.
+        let lower = dist - query.radius() - reference.radius();
+        if lower > self.node_bound(query) { return; }
+
+        // Split the wider of the sets that can be split.
+        let split_query = match (query.children(), reference.children()) {
+            (None, None)       => return,
+            (Some(_), None)    => true,
+            (None, Some(_))    => false,
+            (Some(_), Some(_)) => query.radius() >= reference.radius(),
+        };
+
+        if split_query {
+            self.visit(Node::Point(q), reference, dist);
+            let mut bound = self.bound(q);
+            for child in q.children() {
+                let dist = child.point().distance(*r.point());
+                self.visit(Node::Tree(child), reference, dist);
+                bound = bound.max(self.node_bound(Node::Tree(child)));
+            }
+            self.subtree_bounds.insert(q as *const _, bound);
+        } else {
+            // Visit the reference children nearest first to tighten the
+            // bound early.
+            let mut children: Vec<(&'r Cover<P>, f64)> = r.children()
+                .iter()
+                .map(|child| (child, q.point().distance(*child.point())))
+                .collect();
+            children.sort_by(|a, b| a.1
+                .partial_cmp(&b.1)
+                .expect("sort by distance to target"));
+
+            self.visit(query, Node::Point(r), dist);
+            for (child, dist) in children {
+                self.visit(query, Node::Tree(child), dist);
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// knn_graph
+////////////////////////////////////////////////////////////////////////////////
+/// Returns every point below the given `Cover` with its `k` nearest other
+/// points, in the order of `Iter`.
+pub(crate) fn knn_graph<P>(root: Option<&Cover<P>>, k: usize)
+    -> Vec<(&P, Vec<(&P, f64)>)>
+    where P: Point
+{
+    let found = DualSearch::new(k).excluding_self().run(root, root);
+    in_tree_order(root, found)
+}
+
+/// Returns every point below the given `Cover` with its `k` nearest other
+/// points, in the order of `Iter`, splitting the work over the given number
+/// of threads.
+pub(crate) fn knn_graph_parallel<P>(root: Option<&Cover<P>>, k: usize, threads: usize)
+    -> Vec<(&P, Vec<(&P, f64)>)>
+    where P: Point + Send + Sync
+{
+    assert!(threads > 0, "knn graph needs at least one thread");
+    let reference = match root {
+        Some(root) => root,
+        None       => return Vec::new(),
+    };
+
+    // Split the query points into several sets per thread so that uneven
+    // subtrees still balance out.
+    let mut jobs: VecDeque<Node<P>> = VecDeque::new();
+    jobs.push_back(Node::Tree(reference));
+    while jobs.len() < threads * 4 {
+        match jobs.iter().position(|job| job.children().is_some()) {
+            Some(index) => {
+                let cover = jobs.remove(index).expect("remove split job").cover();
+                jobs.push_back(Node::Point(cover));
+                jobs.extend(cover.children().iter().map(Node::Tree));
+            },
+            None => break,
+        }
+    }
+    let jobs: Vec<Node<P>> = jobs.into_iter().collect();
+
+    let found = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                let jobs = &jobs;
+                scope.spawn(move || {
+                    let mut search = DualSearch::new(k).excluding_self();
+                    for job in jobs.iter().skip(worker).step_by(threads) {
+                        search.search(*job, reference);
+                    }
+                    search.finish()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("join knn graph worker"))
+            .collect()
+    });
+    in_tree_order(root, found)
+}
+
+/// Reorders the neighbor lists of the points below the given `Cover` to the
+/// order of `Iter`.
+fn in_tree_order<'a, 'r, P>(
+    root: Option<&'a Cover<P>>,
+    found: Vec<(&'a P, Vec<(&'r P, f64)>)>)
+    -> Vec<(&'a P, Vec<(&'r P, f64)>)>
+    where P: Point
+{
+    let mut found: HashMap<*const P, Vec<(&'r P, f64)>> = found
+        .into_iter()
+        .map(|(point, neighbors)| (point as *const P, neighbors))
+        .collect();
+    Iter::new(root.map_or(&[], slice::from_ref), 0)
+        .map(|point| {
+            let neighbors = found
+                .remove(&(point as *const P))
+                .unwrap_or_default();
+            (point, neighbors)
+        })
+        .collect()
+}
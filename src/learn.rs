@@ -0,0 +1,355 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Nearest neighbor classification and regression over a `CoverTree` of
+//! labelled points, for any `MetricSpace`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use MetricSpace;
+use Point;
+use CoverTree;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Weighting
+////////////////////////////////////////////////////////////////////////////////
+/// How the nearest neighbors of a query are weighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weighting {
+    /// Every neighbor has the same weight.
+    Uniform,
+    /// Each neighbor is weighted by the reciprocal of its distance. If any
+    /// neighbors coincide with the query, only they are counted.
+    Distance,
+}
+
+impl Weighting {
+    /// Returns the weight of each neighbor at the given distances.
+    fn weights(self, distances: &[f64]) -> Vec<f64> {
+        match self {
+            Weighting::Uniform => vec![1.0; distances.len()],
+            Weighting::Distance if distances.contains(&0.0) => distances
+                .iter()
+                .map(|&dist| if dist == 0.0 { 1.0 } else { 0.0 })
+                .collect(),
+            Weighting::Distance => distances
+                .iter()
+                .map(|&dist| 1.0 / dist)
+                .collect(),
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Labelled
+////////////////////////////////////////////////////////////////////////////////
+/// A point stored with its label. Queries carry no label.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Labelled<P, L> {
+    /// The point.
+    point: P,
+    /// The label of the point.
+    label: Option<L>,
+}
+
+impl<P, L> MetricSpace for Labelled<P, L> where P: MetricSpace {
+    fn distance(self, rhs: Self) -> f64 {
+        self.point.distance(rhs.point)
+    }
+}
+
+/// Returns the labels and distances of the given neighbors.
+fn labels_of<P, L>(neighbors: Vec<(&Labelled<P, L>, f64)>) -> Vec<(L, f64)>
+    where L: Copy
+{
+    neighbors
+        .into_iter()
+        .map(|(n, dist)| (n.label.expect("stored points are labelled"), dist))
+        .collect()
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// KnnClassifier
+////////////////////////////////////////////////////////////////////////////////
+/// Predicts the label of a point by a vote of its `k` nearest labelled points.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::learn::{KnnClassifier, Weighting};
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let training = vec![(1.0, 'a'), (1.2, 'a'), (1.4, 'a'), (5.0, 'b'), (5.5, 'b')];
+///
+/// let classifier: KnnClassifier<f32, char> = KnnClassifier::from_items(
+///     training.into_iter(),
+///     3,
+///     Weighting::Uniform);
+///
+/// assert_eq!(classifier.predict(1.1), Some('a'));
+/// assert_eq!(classifier.predict_batch(vec![0.0, 6.0].into_iter()),
+///     vec![Some('a'), Some('b')]);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnnClassifier<P, L> where P: Point, L: Copy + Eq + Hash {
+    /// The labelled points.
+    tree: CoverTree<Labelled<P, L>>,
+    /// The number of neighbors voting.
+    k: usize,
+    /// The weighting of the votes.
+    weighting: Weighting,
+}
+
+impl<P, L> KnnClassifier<P, L> where P: Point, L: Copy + Eq + Hash {
+    /// Constructs an empty `KnnClassifier` voting with `k` neighbors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn new(k: usize, weighting: Weighting) -> Self {
+        assert!(k > 0, "k-nearest neighbor models require k > 0");
+        KnnClassifier { tree: CoverTree::new(), k, weighting }
+    }
+
+    /// Constructs a `KnnClassifier` voting with `k` neighbors from the given
+    /// labelled points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn from_items<I>(items: I, k: usize, weighting: Weighting) -> Self
+        where I: Iterator<Item=(P, L)>
+    {
+        let mut classifier = KnnClassifier::new(k, weighting);
+        for (point, label) in items {
+            classifier.insert(point, label);
+        }
+        classifier
+    }
+
+    /// Adds a labelled point.
+    pub fn insert(&mut self, point: P, label: L) {
+        self.tree.insert(Labelled { point, label: Some(label) });
+    }
+
+    /// Returns the number of labelled points.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns `true` if there are no labelled points.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the label with the greatest vote among the `k` points nearest
+    /// to the query, or `None` if there are no labelled points. Tied votes go
+    /// to the label of the nearest neighbor among them.
+    pub fn predict(&self, query_point: P) -> Option<L> {
+        let query = Labelled { point: query_point, label: None };
+        let neighbors = labels_of(self.tree.find_k_nearest(query, self.k));
+        vote(&neighbors, self.weighting)
+    }
+
+    /// Returns the prediction for each of the given queries.
+    pub fn predict_batch<I>(&self, query_points: I) -> Vec<Option<L>>
+        where I: Iterator<Item=P>
+    {
+        query_points.map(|query| self.predict(query)).collect()
+    }
+
+    /// Returns the fraction of labelled points predicted correctly from the
+    /// other points alone, or zero if there are no labelled points. The
+    /// neighbors are found together with [`CoverTree::knn_graph`].
+    ///
+    /// [`CoverTree::knn_graph`]: ../struct.CoverTree.html#method.knn_graph
+    pub fn leave_one_out_accuracy(&self) -> f64 {
+        if self.tree.is_empty() { return 0.0; }
+        let correct = self.tree.knn_graph(self.k)
+            .into_iter()
+            .map(|(point, neighbors)| {
+                vote(&labels_of(neighbors), self.weighting) == point.label
+            })
+            .filter(|&correct| correct)
+            .count();
+        correct as f64 / self.tree.len() as f64
+    }
+}
+
+/// Returns the label with the greatest total weight among the given labelled
+/// neighbors, nearest first.
+fn vote<L>(neighbors: &[(L, f64)], weighting: Weighting) -> Option<L>
+    where L: Copy + Eq + Hash
+{
+    let distances: Vec<f64> = neighbors.iter().map(|n| n.1).collect();
+    let mut totals: HashMap<L, f64> = HashMap::new();
+    let weights = weighting.weights(&distances);
+    for (&(label, _), weight) in neighbors.iter().zip(weights) {
+        *totals.entry(label).or_insert(0.0) += weight;
+    }
+
+    // Visit labels nearest first, keeping the first of any tied totals.
+    let mut best: Option<(L, f64)> = None;
+    for &(label, _) in neighbors {
+        let total = totals[&label];
+        if best.map_or(true, |(_, most)| total > most) {
+            best = Some((label, total));
+        }
+    }
+    best.map(|(label, _)| label)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// KnnRegressor
+////////////////////////////////////////////////////////////////////////////////
+/// Predicts the value at a point by the weighted mean of the values of its
+/// `k` nearest points.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::learn::{KnnRegressor, Weighting};
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let training = vec![(1.0, 10.0), (2.0, 20.0), (3.0, 30.0), (10.0, 100.0)];
+///
+/// let regressor: KnnRegressor<f32> = KnnRegressor::from_items(
+///     training.into_iter(),
+///     2,
+///     Weighting::Uniform);
+///
+/// assert_eq!(regressor.predict(1.4), Some(15.0));
+/// assert_eq!(regressor.predict_batch(vec![2.9].into_iter()), vec![Some(25.0)]);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnnRegressor<P> where P: Point {
+    /// The points with their values.
+    tree: CoverTree<Labelled<P, f64>>,
+    /// The number of neighbors averaged.
+    k: usize,
+    /// The weighting of the mean.
+    weighting: Weighting,
+}
+
+impl<P> KnnRegressor<P> where P: Point {
+    /// Constructs an empty `KnnRegressor` averaging `k` neighbors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn new(k: usize, weighting: Weighting) -> Self {
+        assert!(k > 0, "k-nearest neighbor models require k > 0");
+        KnnRegressor { tree: CoverTree::new(), k, weighting }
+    }
+
+    /// Constructs a `KnnRegressor` averaging `k` neighbors from the given
+    /// points and values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn from_items<I>(items: I, k: usize, weighting: Weighting) -> Self
+        where I: Iterator<Item=(P, f64)>
+    {
+        let mut regressor = KnnRegressor::new(k, weighting);
+        for (point, value) in items {
+            regressor.insert(point, value);
+        }
+        regressor
+    }
+
+    /// Adds a point with its value.
+    pub fn insert(&mut self, point: P, value: f64) {
+        self.tree.insert(Labelled { point, label: Some(value) });
+    }
+
+    /// Returns the number of stored points.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// Returns `true` if there are no stored points.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Returns the weighted mean value of the `k` points nearest to the
+    /// query, or `None` if there are no stored points.
+    pub fn predict(&self, query_point: P) -> Option<f64> {
+        let query = Labelled { point: query_point, label: None };
+        let neighbors = labels_of(self.tree.find_k_nearest(query, self.k));
+        mean(&neighbors, self.weighting)
+    }
+
+    /// Returns the prediction for each of the given queries.
+    pub fn predict_batch<I>(&self, query_points: I) -> Vec<Option<f64>>
+        where I: Iterator<Item=P>
+    {
+        query_points.map(|query| self.predict(query)).collect()
+    }
+
+    /// Returns the mean squared error of predicting each stored value from
+    /// the other points alone, or zero if there are fewer than two points.
+    /// The neighbors are found together with [`CoverTree::knn_graph`].
+    ///
+    /// [`CoverTree::knn_graph`]: ../struct.CoverTree.html#method.knn_graph
+    pub fn leave_one_out_error(&self) -> f64 {
+        if self.tree.len() < 2 { return 0.0; }
+        let total: f64 = self.tree.knn_graph(self.k)
+            .into_iter()
+            .map(|(point, neighbors)| {
+                let predicted = mean(&labels_of(neighbors), self.weighting)
+                    .expect("other points to average");
+                let actual = point.label.expect("stored points are labelled");
+                (predicted - actual) * (predicted - actual)
+            })
+            .sum();
+        total / self.tree.len() as f64
+    }
+}
+
+/// Returns the weighted mean of the values of the given neighbors.
+fn mean(neighbors: &[(f64, f64)], weighting: Weighting) -> Option<f64> {
+    if neighbors.is_empty() { return None; }
+    let distances: Vec<f64> = neighbors.iter().map(|n| n.1).collect();
+    let weights = weighting.weights(&distances);
+    let total: f64 = weights.iter().sum();
+    let weighted: f64 = neighbors
+        .iter()
+        .zip(&weights)
+        .map(|(&(value, _), weight)| value * weight)
+        .sum();
+    Some(weighted / total)
+}
@@ -20,6 +20,20 @@
 // Module declarations.
 mod metric;
 mod non_parallel;
+mod iter;
+mod node;
+mod stats;
+mod dot;
+mod query;
+mod join;
+mod centers;
+mod density;
+mod sets;
+mod dimension;
+mod rebuild;
+pub mod cluster;
+pub mod outliers;
+pub mod learn;
 #[cfg(test)]
 mod tests;
 mod cover;
@@ -33,8 +47,16 @@ pub use metric::{
     MetricI64,
     MetricF32,
     MetricF64,
+    DistanceCounter,
+    Counted,
 };
 pub use non_parallel::CoverTree;
+pub use iter::{Iter, IntoIter};
+pub use node::{CoverNode, Children, BreadthFirst, DepthFirst, Levels};
+pub use stats::{Summary, TreeStats};
+pub use query::{Budget, Budgeted, QueryStats};
+pub use density::Kernel;
+pub use rebuild::RebuildPolicy;
 
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -42,6 +64,10 @@ pub use non_parallel::CoverTree;
 ////////////////////////////////////////////////////////////////////////////////
 pub const DEFAULT_SPAN_FACTOR: f64 = 1.3;
 
+/// The span factors tried by `CoverTree::tune_span_factor`.
+pub const SPAN_FACTOR_CANDIDATES: [f64; 8] =
+    [1.1, 1.2, 1.3, 1.5, 1.75, 2.0, 2.5, 3.0];
+
 ////////////////////////////////////////////////////////////////////////////////
 // Point
 ////////////////////////////////////////////////////////////////////////////////
@@ -14,6 +14,9 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 
+use std::cell::Cell;
+
+
 // TODO(Sky): Consider generic return value.
 pub trait MetricSpace<RHS=Self> {
 	/// Calculates the distance between two points.
@@ -67,3 +70,94 @@ auto_impl_metric!(MetricI64, i64);
 // Float types.
 auto_impl_metric!(MetricF32, f32);
 auto_impl_metric!(MetricF64, f64);
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DistanceCounter
+////////////////////////////////////////////////////////////////////////////////
+/// Counts the distance calculations made between the points it has wrapped.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::{CoverTree, DistanceCounter};
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// # 
+/// let counter = DistanceCounter::new();
+/// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+///
+/// let mut cover_tree: CoverTree<_> = nums
+///     .into_iter()
+///     .map(|x| counter.wrap(x))
+///     .collect();
+///
+/// counter.reset();
+/// let nearest = cover_tree.find_nearest(counter.wrap(1.2)).unwrap();
+/// assert_eq!(nearest.point, 1.3);
+/// assert!(counter.count() > 0);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct DistanceCounter {
+	/// The number of distances calculated.
+	count: Cell<usize>,
+}
+
+impl DistanceCounter {
+	/// Constructs a new `DistanceCounter` with a count of zero.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Wraps the given point so that distances calculated from it are counted.
+	pub fn wrap<P>(&self, point: P) -> Counted<'_, P> {
+		Counted { point, counter: self }
+	}
+
+	/// Returns the number of distances calculated since the last reset.
+	pub fn count(&self) -> usize {
+		self.count.get()
+	}
+
+	/// Resets the count to zero.
+	pub fn reset(&self) {
+		self.count.set(0);
+	}
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Counted
+////////////////////////////////////////////////////////////////////////////////
+/// A point whose distance calculations are counted by a [`DistanceCounter`].
+/// Points compare equal if their wrapped points do.
+///
+/// [`DistanceCounter`]: struct.DistanceCounter.html
+#[derive(Debug, Clone, Copy)]
+pub struct Counted<'c, P> {
+	/// The wrapped point.
+	pub point: P,
+	/// The counter to notify of distance calculations.
+	counter: &'c DistanceCounter,
+}
+
+impl<'c, P> PartialEq for Counted<'c, P> where P: PartialEq {
+	fn eq(&self, other: &Self) -> bool {
+		self.point == other.point
+	}
+}
+
+impl<'c, P> MetricSpace for Counted<'c, P> where P: MetricSpace {
+	fn distance(self, rhs: Self) -> f64 {
+		let count = &self.counter.count;
+		count.set(count.get() + 1);
+		self.point.distance(rhs.point)
+	}
+}
@@ -0,0 +1,283 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides a read-only view of the `Cover`s in a `CoverTree` and traversals
+//! over them.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+
+use std::collections::btree_map;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::iter;
+use std::slice;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// CoverNode
+////////////////////////////////////////////////////////////////////////////////
+/// A read-only view of a single node in a [`CoverTree`].
+///
+/// [`CoverTree`]: struct.CoverTree.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let root = cover_tree.root().unwrap();
+/// assert_eq!(root.depth(), 0);
+/// for child in root.children() {
+///     assert!(child.level() <= root.level());
+///     assert_eq!(child.depth(), 1);
+/// }
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CoverNode<'a, P> where P: Point + 'a {
+    /// The viewed `Cover`.
+    cover: &'a Cover<P>,
+    /// The span factor of the tree containing the `Cover`.
+    span_factor: f64,
+    /// The number of edges between the root and the `Cover`.
+    depth: usize,
+}
+
+impl<'a, P> CoverNode<'a, P> where P: Point {
+    /// Constructs a new `CoverNode` viewing the given `Cover`.
+    pub(crate) fn new(cover: &'a Cover<P>, span_factor: f64, depth: usize)
+        -> Self
+    {
+        CoverNode { cover, span_factor, depth }
+    }
+
+    /// Returns the point stored in the node.
+    pub fn point(&self) -> &'a P {
+        self.cover.point()
+    }
+
+    /// Returns the level of the node.
+    pub fn level(&self) -> usize {
+        self.cover.level()
+    }
+
+    /// Returns the number of edges between the root and the node.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the covering radius of the node. Each child of the node is
+    /// expected to lie within this distance of its point.
+    pub fn cover_distance(&self) -> f64 {
+        self.cover.cover_distance(self.span_factor)
+    }
+
+    /// Returns the maximum distance between the node's point and the point of
+    /// any of its descendents.
+    pub fn max_distance(&self) -> f64 {
+        self.cover.max_distance()
+    }
+
+    /// Returns the number of points in the node and its descendents.
+    pub fn size(&self) -> usize {
+        self.cover.size()
+    }
+
+    /// Returns the number of edges on the longest path from the node down to
+    /// one of its descendents.
+    pub fn height(&self) -> usize {
+        self.cover.height()
+    }
+
+    /// Returns `true` if the node has no children.
+    pub fn is_leaf(&self) -> bool {
+        self.cover.children().is_empty()
+    }
+
+    /// Returns an iterator over the children of the node.
+    pub fn children(&self) -> Children<'a, P> {
+        Children {
+            iter: self.cover.children().iter(),
+            span_factor: self.span_factor,
+            depth: self.depth + 1,
+        }
+    }
+}
+
+impl<'a, P> Clone for CoverNode<'a, P> where P: Point {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, P> Copy for CoverNode<'a, P> where P: Point {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Children
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over the children of a [`CoverNode`].
+///
+/// [`CoverNode`]: struct.CoverNode.html
+#[derive(Debug, Clone)]
+pub struct Children<'a, P> where P: Point + 'a {
+    /// The remaining child `Cover`s.
+    iter: slice::Iter<'a, Cover<P>>,
+    /// The span factor of the tree containing the `Cover`s.
+    span_factor: f64,
+    /// The depth of the children.
+    depth: usize,
+}
+
+impl<'a, P> Iterator for Children<'a, P> where P: Point {
+    type Item = CoverNode<'a, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|cover| CoverNode::new(cover, self.span_factor, self.depth))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, P> ExactSizeIterator for Children<'a, P> where P: Point {}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// BreadthFirst
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator visiting the nodes of a [`CoverTree`] in breadth-first order.
+///
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug, Clone)]
+pub struct BreadthFirst<'a, P> where P: Point + 'a {
+    /// The nodes waiting to be visited.
+    queue: VecDeque<CoverNode<'a, P>>,
+}
+
+impl<'a, P> BreadthFirst<'a, P> where P: Point {
+    /// Constructs a `BreadthFirst` traversal starting from the given node.
+    pub(crate) fn new(root: Option<CoverNode<'a, P>>) -> Self {
+        BreadthFirst { queue: root.into_iter().collect() }
+    }
+}
+
+impl<'a, P> Iterator for BreadthFirst<'a, P> where P: Point {
+    type Item = CoverNode<'a, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.children());
+        Some(node)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// DepthFirst
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator visiting the nodes of a [`CoverTree`] in depth-first
+/// pre-order.
+///
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug, Clone)]
+pub struct DepthFirst<'a, P> where P: Point + 'a {
+    /// The remaining children of each node on the path to the current one.
+    stack: Vec<Children<'a, P>>,
+    /// The node to visit first.
+    root: Option<CoverNode<'a, P>>,
+}
+
+impl<'a, P> DepthFirst<'a, P> where P: Point {
+    /// Constructs a `DepthFirst` traversal starting from the given node.
+    pub(crate) fn new(root: Option<CoverNode<'a, P>>) -> Self {
+        DepthFirst { stack: Vec::new(), root }
+    }
+}
+
+impl<'a, P> Iterator for DepthFirst<'a, P> where P: Point {
+    type Item = CoverNode<'a, P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            self.stack.push(root.children());
+            return Some(root);
+        }
+        loop {
+            let next = self.stack.last_mut()?.next();
+            match next {
+                Some(node) => {
+                    if !node.is_leaf() { self.stack.push(node.children()); }
+                    return Some(node);
+                },
+                None => { self.stack.pop(); },
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Levels
+////////////////////////////////////////////////////////////////////////////////
+/// An iterator over the levels of a [`CoverTree`], from the highest level to
+/// the lowest. Each item is a level and all of the nodes at that level.
+///
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug)]
+pub struct Levels<'a, P> where P: Point + 'a {
+    /// The nodes grouped by level.
+    levels: iter::Rev<btree_map::IntoIter<usize, Vec<CoverNode<'a, P>>>>,
+}
+
+impl<'a, P> Levels<'a, P> where P: Point {
+    /// Constructs a `Levels` iterator over the nodes below the given node.
+    pub(crate) fn new(root: Option<CoverNode<'a, P>>) -> Self {
+        let mut levels: BTreeMap<usize, Vec<CoverNode<'a, P>>> = BTreeMap::new();
+        for node in BreadthFirst::new(root) {
+            levels.entry(node.level()).or_default().push(node);
+        }
+        Levels { levels: levels.into_iter().rev() }
+    }
+}
+
+impl<'a, P> Iterator for Levels<'a, P> where P: Point {
+    type Item = (usize, Vec<CoverNode<'a, P>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.levels.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.levels.size_hint()
+    }
+}
+
+impl<'a, P> ExactSizeIterator for Levels<'a, P> where P: Point {}
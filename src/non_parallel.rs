@@ -10,23 +10,41 @@
 
 use Point;
 use cover::Cover;
-use DEFAULT_SPAN_FACTOR;
+use iter::{Iter, IntoIter};
+use node::{CoverNode, BreadthFirst, DepthFirst, Levels};
+use stats::TreeStats;
+use dot;
+use query::{self, Budget, Budgeted, FarthestSearch, QueryStats, Search};
+use join::{self, DualSearch};
+use cluster::{Dendrogram, PointIndex};
+use cluster::linkage;
+use centers;
+use density::{self, Kernel};
+use sets;
+use dimension;
+use rebuild::{Drift, RebuildPolicy, Shape};
+use {DEFAULT_SPAN_FACTOR, SPAN_FACTOR_CANDIDATES};
 
 use std::default;
+use std::fmt::Debug;
+use std::iter;
 use std::mem;
+use std::slice;
 
 
 /// A cover tree containing [`Point`]s of type P.
 ///
 /// [`Point`]: trait.Point.html
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct CoverTree<P> where P: Point {
     /// The root of the tree.
     root: Option<Cover<P>>,
     /// The span factor for each Cover.
     span_factor: f64,
     /// The number of items in the tree.
-    len: usize
+    len: usize,
+    /// The changes tracked for automatic rebuilds, if there is a policy.
+    drift: Option<Drift>,
 }
 
 
@@ -74,8 +92,9 @@ impl<P> CoverTree<P> where P: Point {
     pub fn with_span_factor(span_factor: f64) -> Self {
         CoverTree {
             root: None,
-            span_factor: span_factor,
+            span_factor,
             len: 0,
+            drift: None,
         }
     }
 
@@ -109,7 +128,113 @@ impl<P> CoverTree<P> where P: Point {
         tree
     }
 
-    // TODO(Sky): Can we avoid mut here?
+    /// Returns the span factor from [`SPAN_FACTOR_CANDIDATES`] for which
+    /// nearest neighbor queries cost the fewest distance evaluations, building
+    /// a `CoverTree` over the sample points at each candidate and querying it
+    /// with the query points. Returns [`DEFAULT_SPAN_FACTOR`] if either is
+    /// empty.
+    ///
+    /// The cost is counted by [`find_nearest_with_stats`], which runs the
+    /// same search as [`find_nearest`]. The result can be used to build the
+    /// full tree with [`with_span_factor`], or to rebuild an existing one with
+    /// [`rebuild_with_span_factor`].
+    ///
+    /// [`SPAN_FACTOR_CANDIDATES`]: constant.SPAN_FACTOR_CANDIDATES.html
+    /// [`DEFAULT_SPAN_FACTOR`]: constant.DEFAULT_SPAN_FACTOR.html
+    /// [`find_nearest_with_stats`]: #method.find_nearest_with_stats
+    /// [`find_nearest`]: #method.find_nearest
+    /// [`with_span_factor`]: #method.with_span_factor
+    /// [`rebuild_with_span_factor`]: #method.rebuild_with_span_factor
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::{CoverTree, SPAN_FACTOR_CANDIDATES};
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let sample: Vec<f64> = (0..200).map(|i| ((i * 7919) % 1009) as f64).collect();
+    /// let queries: Vec<f64> = (0..50).map(|i| i as f64 * 20.5).collect();
+    /// 
+    /// let span_factor = CoverTree::tune_span_factor(&sample, &queries);
+    /// assert!(SPAN_FACTOR_CANDIDATES.contains(&span_factor));
+    /// 
+    /// let cover_tree: CoverTree<f64> = CoverTree::with_span_factor(span_factor);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn tune_span_factor(sample: &[P], queries: &[P]) -> f64 {
+        CoverTree::tune_span_factor_among(
+            sample,
+            queries,
+            &SPAN_FACTOR_CANDIDATES)
+    }
+
+    /// Returns the span factor among the given candidates for which nearest
+    /// neighbor queries cost the fewest distance evaluations, as in
+    /// [`tune_span_factor`]. Ties go to the earlier candidate. Returns
+    /// [`DEFAULT_SPAN_FACTOR`] if any of the inputs are empty.
+    ///
+    /// [`tune_span_factor`]: #method.tune_span_factor
+    /// [`DEFAULT_SPAN_FACTOR`]: constant.DEFAULT_SPAN_FACTOR.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if a candidate is not greater than one.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let sample: Vec<f64> = (0..200).map(|i| ((i * 7919) % 1009) as f64).collect();
+    /// let queries: Vec<f64> = (0..50).map(|i| i as f64 * 20.5).collect();
+    /// 
+    /// let span_factor = CoverTree::tune_span_factor_among(&sample, &queries, &[1.3, 2.0]);
+    /// assert!(span_factor == 1.3 || span_factor == 2.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn tune_span_factor_among(
+        sample: &[P],
+        queries: &[P],
+        candidates: &[f64])
+        -> f64
+    {
+        assert!(candidates.iter().all(|&c| c > 1.0),
+            "span factors must be greater than one");
+        if sample.is_empty() || queries.is_empty() {
+            return DEFAULT_SPAN_FACTOR;
+        }
+
+        let mut best: Option<(f64, usize)> = None;
+        for &span_factor in candidates {
+            let mut tree = CoverTree::with_span_factor(span_factor);
+            tree.extend(sample.iter().cloned());
+            let cost: usize = queries
+                .iter()
+                .map(|&query| tree.find_nearest_with_stats(query).1)
+                .map(|stats| stats.distance_evaluations)
+                .sum();
+            if best.map_or(true, |(_, lowest)| cost < lowest) {
+                best = Some((span_factor, cost));
+            }
+        }
+        best.map_or(DEFAULT_SPAN_FACTOR, |(span_factor, _)| span_factor)
+    }
+
     /// Returns the point nearest to the given of [`Point`] in the `CoverTree`.
     ///
     /// [`Point`]: trait.Point.html
@@ -123,7 +248,7 @@ impl<P> CoverTree<P> where P: Point {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
     /// 
     /// let nearest = cover_tree.find_nearest(1.2).unwrap();
     /// assert_eq!(nearest, &1.3f32);
@@ -135,30 +260,38 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn find_nearest<'a>(&'a mut self, query_point: P) -> Option<&'a P> {
-        if let Some(ref mut cover) = self.root {
-            Some(cover.find_nearest(query_point, None))
-        } else {
-            None
-        }
+    pub fn find_nearest(&self, query_point: P) -> Option<&P> {
+        self.find_nearest_with_stats(query_point).0
     }
 
-    /// Returns the number of [`Point`]s in the `CoverTree`.
+    /// Returns the point nearest to the given [`Point`] in the `CoverTree`,
+    /// along with [`QueryStats`] counting the distance calculations, visited
+    /// nodes and pruned subtrees of the search. This is the search
+    /// [`find_nearest`] runs, so the counts are what it costs.
     ///
     /// [`Point`]: trait.Point.html
+    /// [`QueryStats`]: struct.QueryStats.html
+    /// [`find_nearest`]: #method.find_nearest
     /// 
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
-    /// # use covertree::CoverTree;
+    /// # use covertree::{CoverTree, QueryStats};
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
     /// 
-    /// assert_eq!(cover_tree.len(), 4);
+    /// let mut total = QueryStats::default();
+    /// for query in &[1.2, 4.0] {
+    ///     let (nearest, stats) = cover_tree.find_nearest_with_stats(*query);
+    ///     assert!(nearest.is_some());
+    ///     assert!(stats.distance_evaluations <= 4);
+    ///     total += stats;
+    /// }
+    /// assert_eq!(total.queries, 2);
     /// #
     /// #     Ok(())
     /// # }
@@ -167,11 +300,22 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn len(&self) -> usize {
-        self.len
+    pub fn find_nearest_with_stats(&self, query_point: P)
+        -> (Option<&P>, QueryStats)
+    {
+        let (found, stats) = Search::new(query_point, 1).run(self.root.as_ref());
+        (found.first().map(|&(point, _)| point), stats)
     }
 
-    /// Returns the span factor of the `CoverTree`.
+    /// Returns a point whose distance to the given [`Point`] is within a
+    /// factor of `1 + epsilon` of the distance to the nearest point in the
+    /// `CoverTree`. Larger values of `epsilon` prune more of the tree.
+    ///
+    /// [`Point`]: trait.Point.html
+    ///
+    /// # Panics
+    ///
+    /// Panics if `epsilon` is negative.
     /// 
     /// # Example
     ///
@@ -180,9 +324,12 @@ impl<P> CoverTree<P> where P: Point {
     /// # use covertree::CoverTree;
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// # 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::with_span_factor(2.1);
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// assert_eq!(cover_tree.span_factor(), 2.1f64);
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let nearest = cover_tree.find_nearest_approx(4.0, 0.5).unwrap();
+    /// assert!((nearest - 4.0).abs() <= 1.5 * 0.5);
     /// #
     /// #     Ok(())
     /// # }
@@ -191,11 +338,20 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn span_factor(&self) -> f64 {
-        self.span_factor
+    pub fn find_nearest_approx(&self, query_point: P, epsilon: f64)
+        -> Option<&P>
+    {
+        let (found, _) = Search::new(query_point, 1)
+            .with_epsilon(epsilon)
+            .run(self.root.as_ref());
+        found.first().map(|&(point, _)| point)
     }
 
-    /// Returns `true` if the `CoverTree` is empty.
+    /// Returns the `k` points nearest to the given [`Point`] in the
+    /// `CoverTree` with their distances, nearest first. Fewer than `k` points
+    /// are returned if the `CoverTree` holds fewer.
+    ///
+    /// [`Point`]: trait.Point.html
     /// 
     /// # Example
     ///
@@ -206,9 +362,12 @@ impl<P> CoverTree<P> where P: Point {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
     /// 
-    /// assert!(!cover_tree.is_empty());
+    /// let nearest = cover_tree.find_k_nearest(1.2, 2);
+    /// assert_eq!(nearest.len(), 2);
+    /// assert_eq!(nearest[0].0, &1.3f32);
+    /// assert_eq!(nearest[1].0, &1.0f32);
     /// #
     /// #     Ok(())
     /// # }
@@ -217,28 +376,35 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn is_empty(&self) -> bool {
-        self.len == 0
+    pub fn find_k_nearest(&self, query_point: P, k: usize) -> Vec<(&P, f64)> {
+        Search::new(query_point, k).run(self.root.as_ref()).0
     }
 
-    /// Inserts the given [`Point`] into the `CoverTree`.
+    /// Returns the point nearest to the given [`Point`] found before the
+    /// given [`Budget`] runs out. Children are searched nearest first, so the
+    /// result improves the more budget is given.
     ///
     /// [`Point`]: trait.Point.html
+    /// [`Budget`]: struct.Budget.html
     /// 
     /// # Example
     ///
     /// ```rust
     /// # use std::error::Error;
-    /// # use covertree::CoverTree;
+    /// # use covertree::{CoverTree, Budget};
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// # 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::new();
-    ///
-    /// cover_tree.insert(1.3);
-    /// cover_tree.insert(1.52);
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// assert_eq!(cover_tree.len(), 2);
-    /// assert_eq!(cover_tree.find_nearest(1.4).unwrap(), &1.3f32);
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let result = cover_tree.find_nearest_budgeted(1.2, Budget::evaluations(100));
+    /// assert_eq!(result.value, Some(&1.3f32));
+    /// assert!(result.exhaustive);
+    ///
+    /// let result = cover_tree.find_nearest_budgeted(1.2, Budget::evaluations(1));
+    /// assert!(result.value.is_some());
+    /// assert!(!result.exhaustive);
     /// #
     /// #     Ok(())
     /// # }
@@ -247,23 +413,61 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn insert(&mut self, point: P) {
-        let new_cover = Cover::new(point, 1);
-        if let Some(ref mut cover) = self.root {
-            let n = mem::replace(cover, new_cover);
-            mem::replace(cover, n.insert(point, self.span_factor));
-        } else {
-            self.root = Some(new_cover);
+    pub fn find_nearest_budgeted(&self, query_point: P, budget: Budget)
+        -> Budgeted<Option<&P>>
+    {
+        let result = Search::new(query_point, 1)
+            .with_budget(budget)
+            .run_budgeted(self.root.as_ref());
+        Budgeted {
+            value: result.value.first().map(|&(point, _)| point),
+            exhaustive: result.exhaustive,
+            stats: result.stats,
         }
+    }
 
-        self.len += 1;
+    /// Returns the `k` points nearest to the given [`Point`] found before the
+    /// given [`Budget`] runs out, with their distances, nearest first.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`Budget`]: struct.Budget.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::{CoverTree, Budget};
+    /// # use std::time::Duration;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let budget = Budget::timeout(Duration::from_millis(10));
+    /// let result = cover_tree.find_k_nearest_budgeted(4.0, 2, budget);
+    /// assert!(result.value.len() <= 2);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn find_k_nearest_budgeted(&self, query_point: P, k: usize, budget: Budget)
+        -> Budgeted<Vec<(&P, f64)>>
+    {
+        Search::new(query_point, k)
+            .with_budget(budget)
+            .run_budgeted(self.root.as_ref())
     }
 
-    /// Inserts each of the [`Point`]s in the given [`Iterator`] into the 
-    /// `CoverTree`.
+    /// Returns the point nearest to the given [`Point`] for which the given
+    /// predicate returns `true`. The predicate is applied while searching, so
+    /// subtrees are still pruned by distance.
     ///
     /// [`Point`]: trait.Point.html
-    /// [`Iterator`]: http://doc.rust-lang.org/std/iter/trait.Iterator.html
     /// 
     /// # Example
     ///
@@ -272,13 +476,50 @@ impl<P> CoverTree<P> where P: Point {
     /// # use covertree::CoverTree;
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// # 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::new();
+    /// let nums: Vec<i32> = vec![1, 2, 3, 10, 11, 12];
+    /// 
+    /// let cover_tree: CoverTree<i32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let nearest_odd = cover_tree.find_nearest_where(2, |p| p % 2 == 1);
+    /// assert!(nearest_odd == Some(&1) || nearest_odd == Some(&3));
     ///
-    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// let nearest_large = cover_tree.find_nearest_where(2, |p| *p > 10);
+    /// assert_eq!(nearest_large, Some(&11));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn find_nearest_where<F>(&self, query_point: P, predicate: F)
+        -> Option<&P>
+        where F: FnMut(&P) -> bool
+    {
+        self.find_k_nearest_where(query_point, 1, predicate)
+            .first()
+            .map(|&(point, _)| point)
+    }
+
+    /// Returns the `k` points nearest to the given [`Point`] for which the
+    /// given predicate returns `true`, with their distances, nearest first.
     ///
-    /// cover_tree.insert_all(nums.into_iter());
+    /// [`Point`]: trait.Point.html
     /// 
-    /// assert_eq!(cover_tree.len(), 4);
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<i32> = vec![1, 2, 3, 10, 11, 12];
+    /// 
+    /// let cover_tree: CoverTree<i32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let nearest_even = cover_tree.find_k_nearest_where(9, 2, |p| p % 2 == 0);
+    /// assert_eq!(nearest_even, vec![(&10, 1.0), (&12, 3.0)]);
     /// #
     /// #     Ok(())
     /// # }
@@ -287,13 +528,18 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn insert_all<I>(&mut self, points: I) where I: Iterator<Item=P> {
-        for point in points {
-            self.insert(point);
-        }
+    pub fn find_k_nearest_where<F>(&self, query_point: P, k: usize, predicate: F)
+        -> Vec<(&P, f64)>
+        where F: FnMut(&P) -> bool
+    {
+        Search::new(query_point, k)
+            .with_filter(predicate)
+            .run(self.root.as_ref())
+            .0
     }
 
-    /// Removes the given [`Point`] from the `CoverTree`.
+    /// Returns the point farthest from the given [`Point`] in the
+    /// `CoverTree`.
     ///
     /// [`Point`]: trait.Point.html
     /// 
@@ -306,13 +552,10 @@ impl<P> CoverTree<P> where P: Point {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
     /// 
-    /// assert_eq!(cover_tree.len(), 4);
-    ///
-    /// cover_tree.remove(1.3);
-    ///
-    /// assert_eq!(cover_tree.len(), 3);
+    /// assert_eq!(cover_tree.find_farthest(1.2), Some(&4.6f32));
+    /// assert_eq!(cover_tree.find_farthest(4.0), Some(&1.0f32));
     /// #
     /// #     Ok(())
     /// # }
@@ -321,20 +564,16 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn remove(&mut self, point: P) -> Option<P> {
-        if let Some(ref mut cover) = self.root {
-            let removed = cover.remove(point);
-            if removed.is_some() {self.len -= 1;}
-            removed
-        } else {
-            None
-        }
+    pub fn find_farthest(&self, query_point: P) -> Option<&P> {
+        self.find_k_farthest(query_point, 1)
+            .first()
+            .map(|&(point, _)| point)
     }
 
-    /// Removes each [`Point`] in the given [`Iterator`] from the `CoverTree`.
+    /// Returns the `k` points farthest from the given [`Point`] in the
+    /// `CoverTree` with their distances, farthest first.
     ///
     /// [`Point`]: trait.Point.html
-    /// [`Iterator`]: http://doc.rust-lang.org/std/iter/trait.Iterator.html
     /// 
     /// # Example
     ///
@@ -344,14 +583,43 @@ impl<P> CoverTree<P> where P: Point {
     /// # fn try_main() -> Result<(), Box<Error>> {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
-    /// let mut cover_tree = CoverTree::from_items(nums.into_iter());
     /// 
-    /// assert_eq!(cover_tree.len(), 4);
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let farthest = cover_tree.find_k_farthest(4.0, 2);
+    /// assert_eq!(farthest[0].0, &1.0f32);
+    /// assert_eq!(farthest[1].0, &1.3f32);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn find_k_farthest(&self, query_point: P, k: usize) -> Vec<(&P, f64)> {
+        FarthestSearch::new(query_point, k).run(self.root.as_ref())
+    }
+
+    /// Returns the points lying within `radius` of the given [`Point`] in the
+    /// `CoverTree` with their distances, nearest first.
     ///
-    /// let unwanted = vec![1.0, 2.3, 4.6];
-    /// cover_tree.remove_all(unwanted.into_iter());
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
     ///
-    /// assert_eq!(cover_tree.len(), 2);
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let close = cover_tree.find_within(1.2, 0.5);
+    /// assert_eq!(close.len(), 2);
+    /// assert_eq!(close[0].0, &1.3f32);
     /// #
     /// #     Ok(())
     /// # }
@@ -360,13 +628,14 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn remove_all<I>(&mut self, points: I) where I: Iterator<Item=P> {
-        for point in points {
-            self.remove(point);
-        }
+    pub fn find_within(&self, query_point: P, radius: f64) -> Vec<(&P, f64)> {
+        query::find_within(self.root.as_ref(), query_point, radius)
     }
 
-    /// Removes all points from the `CoverTree`.
+    /// Returns every pair of points in the `CoverTree` lying within `radius`
+    /// of each other, with their distances. Each pair is reported once, in no
+    /// particular order. The `CoverTree` is traversed against itself, so far
+    /// apart subtrees are skipped as a whole.
     /// 
     /// # Example
     ///
@@ -377,10 +646,12 @@ impl<P> CoverTree<P> where P: Point {
     /// # 
     /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
     /// 
-    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
     /// 
-    /// cover_tree.clear();
-    /// assert!(cover_tree.is_empty());
+    /// let pairs = cover_tree.self_join(0.5);
+    /// assert_eq!(pairs.len(), 1);
+    /// let (a, b, _) = pairs[0];
+    /// assert!((a, b) == (&1.0, &1.3) || (a, b) == (&1.3, &1.0));
     /// #
     /// #     Ok(())
     /// # }
@@ -389,16 +660,1418 @@ impl<P> CoverTree<P> where P: Point {
     /// #     try_main().unwrap();
     /// # }
     /// ```
-    pub fn clear(&mut self) {
-        self.root = None;
-        self.len = 0;
+    pub fn self_join(&self, radius: f64) -> Vec<(&P, &P, f64)> {
+        join::self_join(self.root.as_ref(), radius)
     }
-}
 
+    /// Returns every pair of a point in this `CoverTree` and a point in the
+    /// other lying within `radius` of each other, with their distances, in no
+    /// particular order. Both trees are traversed together, so far apart
+    /// subtrees are skipped as a whole.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let customers: CoverTree<f32> = vec![1.0, 5.0, 9.0].into_iter().collect();
+    /// let suppliers: CoverTree<f32> = vec![1.2, 8.5, 20.0].into_iter().collect();
+    /// 
+    /// let mut pairs: Vec<(f32, f32)> = customers
+    ///     .join_within(&suppliers, 0.5)
+    ///     .into_iter()
+    ///     .map(|(a, b, _)| (*a, *b))
+    ///     .collect();
+    /// pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(pairs, vec![(1.0, 1.2), (9.0, 8.5)]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn join_within<'a, 'b>(&'a self, other: &'b CoverTree<P>, radius: f64)
+        -> Vec<(&'a P, &'b P, f64)>
+    {
+        join::join_within(self.root.as_ref(), other.root.as_ref(), radius)
+    }
 
-impl<P> default::Default for CoverTree<P> where P: Point {
-    fn default() -> Self {
-        CoverTree::with_span_factor(DEFAULT_SPAN_FACTOR)
+    /// Returns every point in this `CoverTree` paired with its nearest point
+    /// in the other and the distance between them. Both trees are traversed
+    /// together rather than searching the other once per point. Nothing is
+    /// returned if the other `CoverTree` is empty.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let customers: CoverTree<f32> = vec![1.0, 5.0, 9.0].into_iter().collect();
+    /// let suppliers: CoverTree<f32> = vec![1.2, 8.5, 20.0].into_iter().collect();
+    /// 
+    /// let mut pairs: Vec<(f32, f32)> = customers
+    ///     .join_nearest(&suppliers)
+    ///     .into_iter()
+    ///     .map(|(a, b, _)| (*a, *b))
+    ///     .collect();
+    /// pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(pairs, vec![(1.0, 1.2), (5.0, 8.5), (9.0, 8.5)]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn join_nearest<'a, 'b>(&'a self, other: &'b CoverTree<P>)
+        -> Vec<(&'a P, &'b P, f64)>
+    {
+        DualSearch::new(1)
+            .run(self.root.as_ref(), other.root.as_ref())
+            .into_iter()
+            .filter_map(|(point, neighbors)| neighbors
+                .first()
+                .map(|&(nearest, dist)| (point, nearest, dist)))
+            .collect()
     }
-}
 
+    /// Returns the directed Hausdorff distance from this `CoverTree` to the
+    /// other: the largest distance from a point in this `CoverTree` to its
+    /// nearest point in the other. Returns zero if this `CoverTree` is empty,
+    /// and infinity if only the other is.
+    ///
+    /// Each nearest neighbor search stops as soon as it finds a point no
+    /// farther than the largest distance found so far.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let a: CoverTree<f32> = vec![1.0, 2.0, 3.0].into_iter().collect();
+    /// let b: CoverTree<f32> = vec![1.0, 2.0, 3.0, 10.0].into_iter().collect();
+    /// 
+    /// assert_eq!(a.directed_hausdorff(&b), 0.0);
+    /// assert_eq!(b.directed_hausdorff(&a), 7.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn directed_hausdorff(&self, other: &CoverTree<P>) -> f64 {
+        sets::directed_hausdorff(self.iter(), other.root.as_ref())
+    }
+
+    /// Returns the Hausdorff distance between this `CoverTree` and the other:
+    /// the larger of the [`directed_hausdorff`] distances in each direction.
+    ///
+    /// [`directed_hausdorff`]: #method.directed_hausdorff
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let a: CoverTree<f32> = vec![1.0, 2.0, 3.0].into_iter().collect();
+    /// let b: CoverTree<f32> = vec![1.0, 2.0, 3.0, 10.0].into_iter().collect();
+    /// 
+    /// assert_eq!(a.hausdorff(&b), 7.0);
+    /// assert_eq!(b.hausdorff(&a), 7.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn hausdorff(&self, other: &CoverTree<P>) -> f64 {
+        self.directed_hausdorff(other).max(other.directed_hausdorff(self))
+    }
+
+    /// Returns the Chamfer distance between this `CoverTree` and the other:
+    /// the mean distance from a point in this `CoverTree` to its nearest point
+    /// in the other, plus the same mean in the other direction. Returns zero
+    /// if both are empty, and infinity if only one is.
+    ///
+    /// The nearest neighbors are found with [`join_nearest`].
+    ///
+    /// [`join_nearest`]: #method.join_nearest
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let a: CoverTree<f32> = vec![1.0, 2.0, 3.0].into_iter().collect();
+    /// let b: CoverTree<f32> = vec![1.0, 2.0, 3.0, 10.0].into_iter().collect();
+    /// 
+    /// assert_eq!(a.chamfer(&b), 7.0 / 4.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn chamfer(&self, other: &CoverTree<P>) -> f64 {
+        if self.is_empty() && other.is_empty() { return 0.0; }
+        if self.is_empty() || other.is_empty() { return f64::INFINITY; }
+
+        let mean = |from: &CoverTree<P>, to: &CoverTree<P>| {
+            let total: f64 = from.join_nearest(to)
+                .into_iter()
+                .map(|(_, _, dist)| dist)
+                .sum();
+            total / from.len() as f64
+        };
+        mean(self, other) + mean(other, self)
+    }
+
+    /// Returns the k-nearest neighbor graph of the `CoverTree` as an adjacency
+    /// list: every point with its `k` nearest other points and their
+    /// distances, nearest first. Points are listed in the order of [`iter`].
+    /// The `CoverTree` is traversed against itself, so neighboring points
+    /// share the work of their searches.
+    ///
+    /// [`iter`]: #method.iter
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// for (point, neighbors) in cover_tree.knn_graph(1) {
+    ///     let expected = match *point {
+    ///         1.0 => 1.3,
+    ///         1.3 => 1.0,
+    ///         3.5 => 4.6,
+    ///         _   => 3.5,
+    ///     };
+    ///     assert_eq!(neighbors[0].0, &expected);
+    /// }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn knn_graph(&self, k: usize) -> Vec<(&P, Vec<(&P, f64)>)> {
+        join::knn_graph(self.root.as_ref(), k)
+    }
+
+    /// Returns the same k-nearest neighbor graph as [`knn_graph`], splitting
+    /// the work over the given number of threads.
+    ///
+    /// [`knn_graph`]: #method.knn_graph
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.knn_graph_parallel(2, 4), cover_tree.knn_graph(2));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn knn_graph_parallel(&self, k: usize, threads: usize)
+        -> Vec<(&P, Vec<(&P, f64)>)>
+        where P: Send + Sync
+    {
+        join::knn_graph_parallel(self.root.as_ref(), k, threads)
+    }
+
+    /// Returns the edges of a minimum spanning tree over the points of the
+    /// `CoverTree`, each with the distance between its points, from the
+    /// lightest edge to the heaviest.
+    ///
+    /// The tree is found by dual-tree Borůvka: each round links every
+    /// connected component to its nearest other component, pruning subtrees
+    /// that cannot hold a closer point.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.5, 3.5, 4.5];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let weights: Vec<f64> = cover_tree.minimum_spanning_tree()
+    ///     .into_iter()
+    ///     .map(|(_, _, dist)| dist)
+    ///     .collect();
+    /// assert_eq!(weights, vec![0.5, 1.0, 2.0]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn minimum_spanning_tree(&self) -> Vec<(&P, &P, f64)> {
+        let index = PointIndex::new(self.iter());
+        let core = vec![0.0; self.len];
+        let points = index.points();
+        self.spanning_tree(&index, &core)
+            .into_iter()
+            .map(|(a, b, dist)| (points[a], points[b], dist))
+            .collect()
+    }
+
+    /// Returns the [`Dendrogram`] of single-linkage clustering over the
+    /// points of the `CoverTree`, built from its [`minimum_spanning_tree`].
+    ///
+    /// [`Dendrogram`]: cluster/struct.Dendrogram.html
+    /// [`minimum_spanning_tree`]: #method.minimum_spanning_tree
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # use covertree::cluster::Label;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.5, 3.5, 4.5];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// let dendrogram = cover_tree.single_linkage();
+    /// 
+    /// let clusters = dendrogram.cut_at_distance(1.0);
+    /// let label = |x: f32| clusters.iter().find(|c| *c.0 == x).unwrap().1;
+    /// assert_eq!(label(1.0), label(1.5));
+    /// assert_eq!(label(3.5), label(4.5));
+    /// assert!(label(1.0) != label(3.5));
+    /// 
+    /// assert_eq!(dendrogram.cut_into(1)[0].1, Label::Cluster(0));
+    /// assert_eq!(dendrogram.merges().last().unwrap().size, 4);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn single_linkage(&self) -> Dendrogram<'_, P> {
+        let index = PointIndex::new(self.iter());
+        let core = vec![0.0; self.len];
+        let edges = self.spanning_tree(&index, &core);
+        Dendrogram::new(index.points().to_vec(), edges)
+    }
+
+    /// Returns the edges of a minimum spanning tree over the indexed points of
+    /// the `CoverTree` as pairs of positions, lightest first. Each edge is
+    /// weighted by the larger of the distance between its points and their
+    /// `core` distances.
+    pub(crate) fn spanning_tree(&self, index: &PointIndex<P>, core: &[f64])
+        -> Vec<(usize, usize, f64)>
+    {
+        linkage::minimum_spanning_tree(self.root.as_ref(), index, core)
+    }
+
+    /// Returns every point of the `CoverTree` in farthest-first (Gonzalez)
+    /// order, each with its insertion radius: the distance to the nearest
+    /// point before it.
+    ///
+    /// The first point is the root point, with an infinite radius. Each
+    /// following point is the one farthest from all points before it, so the
+    /// radii never increase and the first `k` points are within twice the
+    /// optimal radius of a `k`-center clustering.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![0.0, 1.0, 4.0, 10.0];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let order = cover_tree.farthest_first_order();
+    /// assert_eq!(order.len(), 4);
+    /// assert_eq!(order[0].1, ::std::f64::INFINITY);
+    /// assert!(order.windows(2).all(|w| w[0].1 >= w[1].1));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn farthest_first_order(&self) -> Vec<(&P, f64)> {
+        centers::farthest_first(self, self.len)
+    }
+
+    /// Returns `k` centers chosen by farthest-first traversal, the first `k`
+    /// points of [`farthest_first_order`]. Every point of the `CoverTree` is
+    /// within twice the optimal `k`-center radius of one of them.
+    ///
+    /// Returns every point if there are no more than `k`.
+    ///
+    /// [`farthest_first_order`]: #method.farthest_first_order
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.1, 5.0, 5.1, 9.0, 9.1];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let centers = cover_tree.k_centers(3);
+    /// for group in &[1.0, 5.0, 9.0] {
+    ///     assert!(centers.iter().any(|&&c| (c - group).abs() < 0.5));
+    /// }
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn k_centers(&self, k: usize) -> Vec<&P> {
+        centers::farthest_first(self, k)
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect()
+    }
+
+    /// Returns the kernel density estimate at the given [`Point`]: the mean
+    /// [`Kernel`] weight of the points of the `CoverTree` at the given
+    /// `bandwidth`, or zero if the `CoverTree` is empty.
+    ///
+    /// Subtrees beyond the reach of a compact kernel, or wholly inside a
+    /// top-hat kernel, are counted without measuring their points. Use
+    /// [`kernel_density_approx`] to prune more of the tree.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`Kernel`]: enum.Kernel.html
+    /// [`kernel_density_approx`]: #method.kernel_density_approx
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bandwidth` is not positive.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::{CoverTree, Kernel};
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.5, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.kernel_density(1.2, Kernel::TopHat, 0.5), 0.5);
+    /// assert!(cover_tree.kernel_density(1.2, Kernel::Gaussian, 0.5)
+    ///     > cover_tree.kernel_density(4.0, Kernel::Gaussian, 0.5));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn kernel_density(&self, query_point: P, kernel: Kernel, bandwidth: f64)
+        -> f64
+    {
+        density::kernel_density(
+            self.root.as_ref(),
+            query_point,
+            kernel,
+            bandwidth,
+            0.0)
+    }
+
+    /// Returns the kernel density estimate at the given [`Point`] within
+    /// `tolerance` of the value from [`kernel_density`]. Subtrees whose
+    /// weights vary by at most twice the tolerance are counted at the middle
+    /// of their range, using `max_distance` to bound the distances of their
+    /// points.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`kernel_density`]: #method.kernel_density
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bandwidth` is not positive or `tolerance` is negative.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::{CoverTree, Kernel};
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.5, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let exact = cover_tree.kernel_density(2.0, Kernel::Gaussian, 1.0);
+    /// let approx = cover_tree.kernel_density_approx(2.0, Kernel::Gaussian, 1.0, 0.05);
+    /// assert!((exact - approx).abs() <= 0.05);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn kernel_density_approx(
+        &self,
+        query_point: P,
+        kernel: Kernel,
+        bandwidth: f64,
+        tolerance: f64)
+        -> f64
+    {
+        assert!(tolerance >= 0.0, "tolerance must not be negative");
+        density::kernel_density(
+            self.root.as_ref(),
+            query_point,
+            kernel,
+            bandwidth,
+            tolerance)
+    }
+
+    /// Returns a maximum likelihood estimate of the intrinsic dimension of the
+    /// points in the `CoverTree`, from the distances to their `k` nearest
+    /// neighbors, or `None` if there are too few distinct points.
+    ///
+    /// Cover tree queries slow down as the dimension grows. Data estimated
+    /// well above ten dimensions may be searched as fast by brute force. The
+    /// estimate does not settle a span factor on its own; use
+    /// [`tune_span_factor`] to choose one by measuring queries on a sample.
+    ///
+    /// [`tune_span_factor`]: #method.tune_span_factor
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is less than two.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let line: CoverTree<f64> = (0..500)
+    ///     .map(|i| ((i * 7919) % 1009) as f64 + (i as f64).sqrt())
+    ///     .collect();
+    /// 
+    /// let dimension = line.intrinsic_dimension_mle(10).unwrap();
+    /// assert!((dimension - 1.0).abs() < 0.3);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn intrinsic_dimension_mle(&self, k: usize) -> Option<f64> {
+        assert!(k >= 2, "the dimension estimate needs at least two neighbors");
+        dimension::mle(self.knn_graph(k)
+            .into_iter()
+            .map(|(_, neighbors)| neighbors
+                .into_iter()
+                .map(|(_, dist)| dist)
+                .collect()))
+    }
+
+    /// Returns an estimate of the intrinsic dimension of the points in the
+    /// `CoverTree` from how fast the number of nodes grows from level to
+    /// level, or `None` if the tree has fewer than two levels.
+    ///
+    /// The estimate needs no distance evaluations, only a walk over the
+    /// nodes, but it is coarse: it reflects the scales the tree resolves,
+    /// which depend on the span factor. [`intrinsic_dimension_mle`] is more
+    /// accurate, and [`tune_span_factor`] chooses a span factor.
+    ///
+    /// [`intrinsic_dimension_mle`]: #method.intrinsic_dimension_mle
+    /// [`tune_span_factor`]: #method.tune_span_factor
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let line: CoverTree<f64> = (0..500).map(|i| i as f64 * 10.0).collect();
+    /// 
+    /// let dimension = line.intrinsic_dimension_levels().unwrap();
+    /// assert!(dimension > 0.0 && dimension < 3.0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn intrinsic_dimension_levels(&self) -> Option<f64> {
+        dimension::level_growth(self.levels(), self.span_factor)
+    }
+
+    /// Returns `true` if the `CoverTree` contains the given [`Point`].
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert!(cover_tree.contains(&3.5));
+    /// assert!(!cover_tree.contains(&3.6));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn contains(&self, point: &P) -> bool {
+        self.root.as_ref().is_some_and(|cover| cover.contains(point))
+    }
+
+    /// Returns the number of [`Point`]s in the `CoverTree`.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.len(), 4);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the span factor of the `CoverTree`.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::with_span_factor(2.1);
+    /// 
+    /// assert_eq!(cover_tree.span_factor(), 2.1f64);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn span_factor(&self) -> f64 {
+        self.span_factor
+    }
+
+    /// Returns `true` if the `CoverTree` is empty.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert!(!cover_tree.is_empty());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts the given [`Point`] into the `CoverTree`.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::new();
+    ///
+    /// cover_tree.insert(1.3);
+    /// cover_tree.insert(1.52);
+    /// 
+    /// assert_eq!(cover_tree.len(), 2);
+    /// assert_eq!(cover_tree.find_nearest(1.4).unwrap(), &1.3f32);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn insert(&mut self, point: P) {
+        self.insert_untracked(point);
+        self.note_change();
+    }
+
+    /// Inserts the given [`Point`] without counting it against the
+    /// [`RebuildPolicy`].
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`RebuildPolicy`]: struct.RebuildPolicy.html
+    fn insert_untracked(&mut self, point: P) {
+        let new_cover = Cover::new(point, 1);
+        if let Some(ref mut cover) = self.root {
+            let n = mem::replace(cover, new_cover);
+            *cover = n.insert(point, self.span_factor);
+        } else {
+            self.root = Some(new_cover);
+        }
+
+        self.len += 1;
+    }
+
+    /// Inserts each of the [`Point`]s in the given [`Iterator`] into the 
+    /// `CoverTree`.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`Iterator`]: http://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::new();
+    ///
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    ///
+    /// cover_tree.insert_all(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.len(), 4);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn insert_all<I>(&mut self, points: I) where I: Iterator<Item=P> {
+        for point in points {
+            self.insert(point);
+        }
+    }
+
+    /// Removes the given [`Point`] from the `CoverTree`, returning it if it
+    /// was present.
+    ///
+    /// Every point below the removed one is reinserted one at a time, so the
+    /// cost grows with the size of its subtree. Removing a leaf is cheap, but
+    /// removing the root reinserts every other point, costing as much as
+    /// building the tree again.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.len(), 4);
+    ///
+    /// cover_tree.remove(1.3);
+    ///
+    /// assert_eq!(cover_tree.len(), 3);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn remove(&mut self, point: P) -> Option<P> {
+        let at_root = self.root
+            .as_ref()
+            .is_some_and(|cover| *cover.point() == point);
+
+        let removed = if at_root {
+            self.root.take()
+        } else {
+            self.root.as_mut().and_then(|cover| cover.remove(point))
+        };
+
+        // Reinsert the descendents of the removed Cover.
+        let (removed, children) = removed?.into_parts();
+        let orphans: Vec<P> = IntoIter::new(children, 0).collect();
+        self.len -= 1 + orphans.len();
+        for orphan in orphans {
+            self.insert_untracked(orphan);
+        }
+        self.note_change();
+        Some(removed)
+    }
+
+    /// Removes each [`Point`] in the given [`Iterator`] from the `CoverTree`.
+    /// Each removal costs as much as [`remove`], so removing many points near
+    /// the root can cost more than building a new tree from the points kept,
+    /// as [`retain`] does.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`Iterator`]: http://doc.rust-lang.org/std/iter/trait.Iterator.html
+    /// [`remove`]: #method.remove
+    /// [`retain`]: #method.retain
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// let mut cover_tree = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.len(), 4);
+    ///
+    /// let unwanted = vec![1.0, 2.3, 4.6];
+    /// cover_tree.remove_all(unwanted.into_iter());
+    ///
+    /// assert_eq!(cover_tree.len(), 2);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn remove_all<I>(&mut self, points: I) where I: Iterator<Item=P> {
+        for point in points {
+            self.remove(point);
+        }
+    }
+
+    /// Returns an iterator over references to the [`Point`]s in the
+    /// `CoverTree`.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let mut points: Vec<f32> = cover_tree.iter().cloned().collect();
+    /// points.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(points, vec![1.0, 1.3, 3.5, 4.6]);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, P> {
+        match self.root {
+            Some(ref cover) => Iter::new(slice::from_ref(cover), self.len),
+            None            => Iter::new(&[], 0),
+        }
+    }
+
+    /// Returns a [`CoverNode`] viewing the root of the `CoverTree`, or `None`
+    /// if the `CoverTree` is empty.
+    ///
+    /// [`CoverNode`]: struct.CoverNode.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let root = cover_tree.root().unwrap();
+    /// assert_eq!(root.depth(), 0);
+    /// assert!(root.max_distance() <= 3.6);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn root(&self) -> Option<CoverNode<'_, P>> {
+        self.root
+            .as_ref()
+            .map(|cover| CoverNode::new(cover, self.span_factor, 0))
+    }
+
+    /// Returns an iterator over the levels of the `CoverTree`, from the
+    /// highest level to the lowest. Each item holds a level and every
+    /// [`CoverNode`] at that level.
+    ///
+    /// [`CoverNode`]: struct.CoverNode.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let total: usize = cover_tree.levels().map(|(_, nodes)| nodes.len()).sum();
+    /// assert_eq!(total, 4);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn levels(&self) -> Levels<'_, P> {
+        Levels::new(self.root())
+    }
+
+    /// Returns an iterator visiting every [`CoverNode`] of the `CoverTree` in
+    /// breadth-first order.
+    ///
+    /// [`CoverNode`]: struct.CoverNode.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let depths: Vec<usize> = cover_tree.breadth_first().map(|n| n.depth()).collect();
+    /// assert_eq!(depths.len(), 4);
+    /// assert!(depths.windows(2).all(|w| w[0] <= w[1]));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn breadth_first(&self) -> BreadthFirst<'_, P> {
+        BreadthFirst::new(self.root())
+    }
+
+    /// Returns an iterator visiting every [`CoverNode`] of the `CoverTree` in
+    /// depth-first pre-order.
+    ///
+    /// [`CoverNode`]: struct.CoverNode.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let first = cover_tree.depth_first().next().unwrap();
+    /// assert_eq!(first.depth(), 0);
+    /// assert_eq!(cover_tree.depth_first().count(), 4);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn depth_first(&self) -> DepthFirst<'_, P> {
+        DepthFirst::new(self.root())
+    }
+
+    /// Returns a [`TreeStats`] report on the shape of the `CoverTree`.
+    ///
+    /// [`TreeStats`]: struct.TreeStats.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let stats = cover_tree.stats();
+    /// assert_eq!(stats.node_count, 4);
+    /// assert!(stats.leaf_count >= 1);
+    /// assert!(stats.fan_out.max >= stats.fan_out.min);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::new(self.root())
+    }
+
+    /// Renders the structure of the `CoverTree` in the Graphviz DOT language.
+    /// Each node is labelled with its point, level and covering radius, and
+    /// each edge runs from a parent to one of its children. Nodes deeper than
+    /// `max_depth` are left out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// let dot = cover_tree.to_dot(None);
+    /// assert!(dot.starts_with("digraph CoverTree {"));
+    /// assert_eq!(dot.matches(" -> ").count(), 3);
+    ///
+    /// let root_only = cover_tree.to_dot(Some(0));
+    /// assert_eq!(root_only.matches(" -> ").count(), 0);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn to_dot(&self, max_depth: Option<usize>) -> String where P: Debug {
+        dot::to_dot(self.root(), max_depth)
+    }
+
+    /// Removes all points from the `CoverTree`.
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// cover_tree.clear();
+    /// assert!(cover_tree.is_empty());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+        self.drift = self.drift.map(|drift| Drift::new(drift.policy(), None));
+    }
+
+    /// Retains only the [`Point`]s for which the given predicate returns
+    /// `true`. The `CoverTree` is rebuilt from the retained points.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// cover_tree.retain(|p| *p > 2.0);
+    /// assert_eq!(cover_tree.len(), 2);
+    /// assert!(!cover_tree.contains(&1.3));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F) where F: FnMut(&P) -> bool {
+        let points = self.drain();
+        for point in points.filter(|p| f(p)) {
+            self.insert_untracked(point);
+        }
+    }
+
+    /// Removes all [`Point`]s from the `CoverTree`, returning them as an
+    /// iterator. The span factor of the `CoverTree` is kept.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// 
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    /// 
+    /// assert_eq!(cover_tree.drain().count(), 4);
+    /// assert!(cover_tree.is_empty());
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn drain(&mut self) -> IntoIter<P> {
+        let mut empty = CoverTree::with_span_factor(self.span_factor);
+        empty.drift = self.drift.map(|drift| Drift::new(drift.policy(), None));
+        mem::replace(self, empty).into_iter()
+    }
+
+    /// Rebuilds the `CoverTree` from its [`Point`]s, keeping its span factor.
+    ///
+    /// Insertion only ever adds leaves and promotes them, so a tree that has
+    /// seen many insertions and removals can grow deeper and slower to query
+    /// than one built from the same points at once. The points are reinserted
+    /// in [`farthest_first_order`], placing well spread points near the root.
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`farthest_first_order`]: #method.farthest_first_order
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let mut cover_tree: CoverTree<f32> = (0..100).map(|i| i as f32).collect();
+    ///
+    /// cover_tree.rebuild();
+    ///
+    /// assert_eq!(cover_tree.len(), 100);
+    /// assert_eq!(cover_tree.find_nearest(41.8).unwrap(), &42.0f32);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn rebuild(&mut self) {
+        let span_factor = self.span_factor;
+        self.rebuild_with_span_factor(span_factor);
+    }
+
+    /// Rebuilds the `CoverTree` from its [`Point`]s with the given span factor,
+    /// as in [`rebuild`].
+    ///
+    /// [`Point`]: trait.Point.html
+    /// [`rebuild`]: #method.rebuild
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::CoverTree;
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let nums: Vec<f32> = vec![1.0, 1.3, 3.5, 4.6];
+    /// 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+    ///
+    /// cover_tree.rebuild_with_span_factor(2.0);
+    ///
+    /// assert_eq!(cover_tree.span_factor(), 2.0);
+    /// assert_eq!(cover_tree.len(), 4);
+    /// assert!(cover_tree.contains(&3.5));
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn rebuild_with_span_factor(&mut self, span_factor: f64) {
+        let points: Vec<P> = self.farthest_first_order()
+            .into_iter()
+            .map(|(&point, _)| point)
+            .collect();
+
+        let mut tree = CoverTree::with_span_factor(span_factor);
+        for point in points {
+            tree.insert_untracked(point);
+        }
+        tree.drift = self.drift.map(|drift| {
+            Drift::new(drift.policy(), Shape::measure(tree.root.as_ref()))
+        });
+        *self = tree;
+    }
+
+    /// Sets the [`RebuildPolicy`] under which the `CoverTree` rebuilds itself
+    /// as points are inserted and removed, or stops rebuilding automatically
+    /// if `None` is given. Growth is measured against the current tree.
+    ///
+    /// [`RebuildPolicy`]: struct.RebuildPolicy.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::error::Error;
+    /// # use covertree::{CoverTree, RebuildPolicy};
+    /// # fn try_main() -> Result<(), Box<Error>> {
+    /// # 
+    /// let mut cover_tree: CoverTree<f32> = CoverTree::new();
+    ///
+    /// let policy = RebuildPolicy::every(100)
+    ///     .with_max_depth_growth(1.5)
+    ///     .with_max_cost_growth(2.0);
+    /// cover_tree.set_rebuild_policy(Some(policy));
+    ///
+    /// cover_tree.insert_all((0..1000).map(|i| i as f32));
+    ///
+    /// assert_eq!(cover_tree.rebuild_policy(), Some(policy));
+    /// assert_eq!(cover_tree.len(), 1000);
+    /// #
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    pub fn set_rebuild_policy(&mut self, policy: Option<RebuildPolicy>) {
+        self.drift = policy.map(|policy| {
+            Drift::new(policy, Shape::measure(self.root.as_ref()))
+        });
+    }
+
+    /// Returns the [`RebuildPolicy`] of the `CoverTree`, if it has one.
+    ///
+    /// [`RebuildPolicy`]: struct.RebuildPolicy.html
+    pub fn rebuild_policy(&self) -> Option<RebuildPolicy> {
+        self.drift.map(|drift| drift.policy())
+    }
+
+    /// Counts an insertion or removal against the [`RebuildPolicy`], rebuilding
+    /// the `CoverTree` if it is due to be measured and has drifted.
+    ///
+    /// [`RebuildPolicy`]: struct.RebuildPolicy.html
+    fn note_change(&mut self) {
+        let due = self.drift.as_mut().is_some_and(|drift| drift.note_change());
+        if !due { return; }
+
+        let shape = Shape::measure(self.root.as_ref());
+        if self.drift.as_mut().is_some_and(|drift| drift.has_drifted(shape)) {
+            self.rebuild();
+        }
+    }
+}
+
+
+impl<P> PartialEq for CoverTree<P> where P: Point {
+    /// Compares the structure and span factor of the trees, ignoring their
+    /// rebuild policies.
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root &&
+            self.span_factor == other.span_factor &&
+            self.len == other.len
+    }
+}
+
+
+impl<P> default::Default for CoverTree<P> where P: Point {
+    fn default() -> Self {
+        CoverTree::with_span_factor(DEFAULT_SPAN_FACTOR)
+    }
+}
+
+impl<P> IntoIterator for CoverTree<P> where P: Point {
+    type Item = P;
+    type IntoIter = IntoIter<P>;
+
+    /// Returns an iterator over the [`Point`]s in the `CoverTree`, consuming
+    /// the tree.
+    ///
+    /// [`Point`]: trait.Point.html
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.root.into_iter().collect(), self.len)
+    }
+}
+
+impl<'a, P> IntoIterator for &'a CoverTree<P> where P: Point {
+    type Item = &'a P;
+    type IntoIter = Iter<'a, P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<P> iter::FromIterator<P> for CoverTree<P> where P: Point {
+    fn from_iter<I>(points: I) -> Self where I: IntoIterator<Item=P> {
+        CoverTree::from_items(points.into_iter())
+    }
+}
+
+impl<P> iter::Extend<P> for CoverTree<P> where P: Point {
+    fn extend<I>(&mut self, points: I) where I: IntoIterator<Item=P> {
+        self.insert_all(points.into_iter());
+    }
+}
+
+impl<'a, P> iter::Extend<&'a P> for CoverTree<P> where P: Point + 'a {
+    fn extend<I>(&mut self, points: I) where I: IntoIterator<Item=&'a P> {
+        self.insert_all(points.into_iter().cloned());
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Outlier scores answering their neighborhood queries with a `CoverTree`, so
+//! they work for any `MetricSpace`. Higher scores are more anomalous.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use CoverTree;
+use cluster::PointIndex;
+
+
+/// Added to mean reachability distances so duplicate points have a finite
+/// local density, as in scikit-learn.
+const REACH_EPSILON: f64 = 1e-10;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// knn_distance_scores
+////////////////////////////////////////////////////////////////////////////////
+/// Returns each point of a [`CoverTree`] with the distance to its `k`-th
+/// nearest other point, in the order of [`CoverTree::iter`]. Points with
+/// fewer than `k` others use the farthest one, or zero if there are none.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+/// [`CoverTree::iter`]: ../struct.CoverTree.html#method.iter
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::outliers;
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 1.2, 1.3, 9.0];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let scores = outliers::knn_distance_scores(&cover_tree, 2);
+/// let most = scores.iter().max_by(|a, b| a.1.partial_cmp(&b.1).unwrap()).unwrap();
+/// assert_eq!(*most.0, 9.0);
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn knn_distance_scores<P>(tree: &CoverTree<P>, k: usize) -> Vec<(&P, f64)>
+    where P: Point
+{
+    assert!(k > 0, "outlier scores require at least one neighbor");
+    tree.knn_graph(k)
+        .into_iter()
+        .map(|(point, neighbors)| (point, kth_distance(&neighbors)))
+        .collect()
+}
+
+/// Returns the distance from a query point to its `k`-th nearest point of a
+/// [`CoverTree`], scoring it against the points of the tree. A query equal to
+/// a point of the tree counts that point as a neighbor.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+///
+/// # Panics
+///
+/// Panics if `k` is zero.
+pub fn knn_distance_score<P>(tree: &CoverTree<P>, query_point: P, k: usize)
+    -> f64
+    where P: Point
+{
+    assert!(k > 0, "outlier scores require at least one neighbor");
+    kth_distance(&tree.find_k_nearest(query_point, k))
+}
+
+/// Returns the distance of the last of the given neighbors, or zero if there
+/// are none.
+fn kth_distance<P>(neighbors: &[(&P, f64)]) -> f64 {
+    neighbors.last().map_or(0.0, |&(_, dist)| dist)
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// LocalOutlierFactor
+////////////////////////////////////////////////////////////////////////////////
+/// Local Outlier Factor scores of the points of a [`CoverTree`].
+///
+/// The reachability distance from a point to a neighbor is the larger of
+/// their distance and the neighbor's `k`-th nearest neighbor distance. The
+/// local density of a point is the reciprocal of the mean reachability
+/// distance to its `k` nearest neighbors, and its LOF is the mean density of
+/// those neighbors divided by its own. Scores near one are typical, and
+/// scores well above one mark points in sparser regions than their
+/// neighbors.
+///
+/// Ties at the `k`-th nearest distance are broken arbitrarily, so exactly `k`
+/// neighbors are used.
+///
+/// [`CoverTree`]: ../struct.CoverTree.html
+///
+/// # Example
+///
+/// ```rust
+/// # use std::error::Error;
+/// # use covertree::CoverTree;
+/// # use covertree::outliers::LocalOutlierFactor;
+/// # fn try_main() -> Result<(), Box<Error>> {
+/// #
+/// let nums: Vec<f32> = vec![1.0, 1.1, 1.2, 1.3, 1.4, 3.0];
+///
+/// let cover_tree: CoverTree<f32> = CoverTree::from_items(nums.into_iter());
+///
+/// let lof = LocalOutlierFactor::new(&cover_tree, 2);
+/// let score = |x: f32| lof.scores().into_iter().find(|s| *s.0 == x).unwrap().1;
+/// assert!(score(3.0) > 2.0);
+/// assert!(score(1.2) < 1.5);
+///
+/// assert!(lof.score(8.0) > lof.score(1.25));
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocalOutlierFactor<'a, P> where P: Point + 'a {
+    /// The scored tree.
+    tree: &'a CoverTree<P>,
+    /// The number of neighbors used.
+    k: usize,
+    /// The positions of the points of the tree.
+    index: PointIndex<'a, P>,
+    /// The distance from each point to its `k`-th nearest neighbor.
+    k_distances: Vec<f64>,
+    /// The local density of each point.
+    densities: Vec<f64>,
+    /// The LOF score of each point.
+    scores: Vec<f64>,
+}
+
+impl<'a, P> LocalOutlierFactor<'a, P> where P: Point {
+    /// Computes the LOF scores of the points of the given tree using their
+    /// `k` nearest neighbors, found with [`CoverTree::knn_graph`].
+    ///
+    /// [`CoverTree::knn_graph`]: ../struct.CoverTree.html#method.knn_graph
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn new(tree: &'a CoverTree<P>, k: usize) -> Self {
+        assert!(k > 0, "outlier scores require at least one neighbor");
+
+        let index = PointIndex::new(tree.iter());
+        let neighbors: Vec<Vec<(usize, f64)>> = tree.knn_graph(k)
+            .into_iter()
+            .map(|(_, neighbors)| neighbors
+                .into_iter()
+                .map(|(point, dist)| (index.position(point), dist))
+                .collect())
+            .collect();
+
+        let k_distances: Vec<f64> = neighbors
+            .iter()
+            .map(|n| n.last().map_or(0.0, |&(_, dist)| dist))
+            .collect();
+        let densities: Vec<f64> = neighbors
+            .iter()
+            .map(|n| density(n, &k_distances))
+            .collect();
+        let scores = neighbors
+            .iter()
+            .zip(&densities)
+            .map(|(n, &own)| factor(n, own, &densities))
+            .collect();
+
+        LocalOutlierFactor { tree, k, index, k_distances, densities, scores }
+    }
+
+    /// Returns each point of the tree with its LOF score, in the order of
+    /// [`CoverTree::iter`].
+    ///
+    /// [`CoverTree::iter`]: ../struct.CoverTree.html#method.iter
+    pub fn scores(&self) -> Vec<(&'a P, f64)> {
+        self.index.points()
+            .iter()
+            .cloned()
+            .zip(self.scores.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the LOF score of a query point against the points of the tree,
+    /// without adding it to them. A query equal to a point of the tree counts
+    /// that point as a neighbor.
+    pub fn score(&self, query_point: P) -> f64 {
+        let neighbors: Vec<(usize, f64)> = self.tree
+            .find_k_nearest(query_point, self.k)
+            .into_iter()
+            .map(|(point, dist)| (self.index.position(point), dist))
+            .collect();
+        let own = density(&neighbors, &self.k_distances);
+        factor(&neighbors, own, &self.densities)
+    }
+}
+
+/// Returns the local density of a point with the given neighbors.
+fn density(neighbors: &[(usize, f64)], k_distances: &[f64]) -> f64 {
+    if neighbors.is_empty() { return 1.0 / REACH_EPSILON; }
+    let reach: f64 = neighbors
+        .iter()
+        .map(|&(i, dist)| dist.max(k_distances[i]))
+        .sum();
+    1.0 / (reach / neighbors.len() as f64 + REACH_EPSILON)
+}
+
+/// Returns the LOF score of a point with the given neighbors and density.
+fn factor(neighbors: &[(usize, f64)], own: f64, densities: &[f64]) -> f64 {
+    if neighbors.is_empty() { return 1.0; }
+    let total: f64 = neighbors.iter().map(|&(i, _)| densities[i]).sum();
+    total / neighbors.len() as f64 / own
+}
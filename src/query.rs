@@ -0,0 +1,426 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides the instrumented and budgeted nearest neighbor search, the
+//! farthest neighbor search and the range search over `Cover`s.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+
+use std::ops;
+use std::time::{Duration, Instant};
+
+
+////////////////////////////////////////////////////////////////////////////////
+// QueryStats
+////////////////////////////////////////////////////////////////////////////////
+/// Counts of the work done by one or more queries on a [`CoverTree`].
+///
+/// Statistics of several queries can be accumulated with `+=`.
+///
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueryStats {
+    /// The number of queries counted.
+    pub queries: usize,
+    /// The number of calls to `MetricSpace::distance`.
+    pub distance_evaluations: usize,
+    /// The number of nodes whose children were examined.
+    pub nodes_visited: usize,
+    /// The number of subtrees skipped because they cannot hold a closer
+    /// point than the best found so far.
+    pub subtrees_pruned: usize,
+    /// The deepest level of recursion reached, with the root at depth zero.
+    pub max_depth: usize,
+}
+
+impl ops::Add for QueryStats {
+    type Output = QueryStats;
+
+    fn add(mut self, other: QueryStats) -> QueryStats {
+        self += other;
+        self
+    }
+}
+
+impl ops::AddAssign for QueryStats {
+    fn add_assign(&mut self, other: QueryStats) {
+        self.queries += other.queries;
+        self.distance_evaluations += other.distance_evaluations;
+        self.nodes_visited += other.nodes_visited;
+        self.subtrees_pruned += other.subtrees_pruned;
+        self.max_depth = self.max_depth.max(other.max_depth);
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Budget
+////////////////////////////////////////////////////////////////////////////////
+/// A limit on the work a query on a [`CoverTree`] may do before it returns
+/// the best result found so far.
+///
+/// [`CoverTree`]: struct.CoverTree.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Budget {
+    /// The maximum number of distance calculations.
+    max_evaluations: Option<usize>,
+    /// The time after which no more distances are calculated.
+    deadline: Option<Instant>,
+}
+
+impl Budget {
+    /// Constructs a `Budget` that never runs out.
+    pub fn unlimited() -> Self {
+        Default::default()
+    }
+
+    /// Constructs a `Budget` of the given number of distance calculations.
+    pub fn evaluations(max_evaluations: usize) -> Self {
+        Budget::unlimited().with_evaluations(max_evaluations)
+    }
+
+    /// Constructs a `Budget` that runs out at the given time.
+    pub fn deadline(deadline: Instant) -> Self {
+        Budget::unlimited().with_deadline(deadline)
+    }
+
+    /// Constructs a `Budget` that runs out once the given duration has passed
+    /// from now.
+    pub fn timeout(timeout: Duration) -> Self {
+        Budget::deadline(Instant::now() + timeout)
+    }
+
+    /// Limits the `Budget` to the given number of distance calculations.
+    pub fn with_evaluations(mut self, max_evaluations: usize) -> Self {
+        self.max_evaluations = Some(max_evaluations);
+        self
+    }
+
+    /// Limits the `Budget` to end at the given time.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Returns `true` if no more distances may be calculated after the given
+    /// number of them.
+    fn is_spent(&self, evaluations: usize) -> bool {
+        self.max_evaluations.is_some_and(|max| evaluations >= max) ||
+            self.deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Budgeted
+////////////////////////////////////////////////////////////////////////////////
+/// The result of a query on a [`CoverTree`] run under a [`Budget`].
+///
+/// [`CoverTree`]: struct.CoverTree.html
+/// [`Budget`]: struct.Budget.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budgeted<T> {
+    /// The best result found before the budget ran out.
+    pub value: T,
+    /// Whether the search finished, making the result exact.
+    pub exhaustive: bool,
+    /// The work done by the query.
+    pub stats: QueryStats,
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Search
+////////////////////////////////////////////////////////////////////////////////
+/// A k-nearest neighbor search over a `Cover` and its descendents, only
+/// finding points accepted by the filter `F`.
+pub(crate) struct Search<'a, P, F = fn(&P) -> bool> where P: Point + 'a {
+    /// The query point.
+    query: P,
+    /// The number of neighbors to find.
+    k: usize,
+    /// The factor by which found points may be farther than the true
+    /// neighbors, `1 + ε`.
+    slack: f64,
+    /// The nearest points found so far and their distances, nearest first.
+    found: Vec<(&'a P, f64)>,
+    /// The predicate points must satisfy to be found.
+    filter: F,
+    /// The work the search may do.
+    budget: Budget,
+    /// Whether the search stopped because the budget ran out.
+    exhausted: bool,
+    /// The work done so far.
+    stats: QueryStats,
+}
+
+impl<'a, P> Search<'a, P> where P: Point {
+    /// Constructs a new `Search` for the `k` nearest points to the query.
+    pub(crate) fn new(query: P, k: usize) -> Self {
+        Search {
+            query,
+            k,
+            slack: 1.0,
+            found: Vec::with_capacity(k),
+            filter: |_| true,
+            budget: Budget::unlimited(),
+            exhausted: false,
+            stats: QueryStats { queries: 1, ..Default::default() },
+        }
+    }
+
+    /// Only finds points for which the given predicate returns `true`.
+    pub(crate) fn with_filter<G>(self, filter: G) -> Search<'a, P, G>
+        where G: FnMut(&P) -> bool
+    {
+        Search {
+            query: self.query,
+            k: self.k,
+            slack: self.slack,
+            found: self.found,
+            filter,
+            budget: self.budget,
+            exhausted: self.exhausted,
+            stats: self.stats,
+        }
+    }
+}
+
+impl<'a, P, F> Search<'a, P, F> where P: Point, F: FnMut(&P) -> bool {
+    /// Allows the found points to be up to `1 + epsilon` times farther from
+    /// the query than the true nearest neighbors.
+    pub(crate) fn with_epsilon(mut self, epsilon: f64) -> Self {
+        assert!(epsilon >= 0.0, "approximation epsilon must not be negative");
+        self.slack = 1.0 + epsilon;
+        self
+    }
+
+    /// Stops the search once the given budget runs out.
+    pub(crate) fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Runs the search over the given `Cover` and its descendents.
+    pub(crate) fn run(self, root: Option<&'a Cover<P>>)
+        -> (Vec<(&'a P, f64)>, QueryStats)
+    {
+        let result = self.run_budgeted(root);
+        (result.value, result.stats)
+    }
+
+    /// Runs the search over the given `Cover` and its descendents, reporting
+    /// whether it finished within its budget.
+    pub(crate) fn run_budgeted(mut self, root: Option<&'a Cover<P>>)
+        -> Budgeted<Vec<(&'a P, f64)>>
+    {
+        if self.k > 0 {
+            if let Some(root) = root {
+                if let Some(dist) = self.distance(root.point()) {
+                    self.visit(root, dist, 0);
+                }
+            }
+        }
+        Budgeted {
+            value: self.found,
+            exhaustive: !self.exhausted,
+            stats: self.stats,
+        }
+    }
+
+    /// Returns the distance from the query to the given point, or `None` if
+    /// the budget has run out.
+    fn distance(&mut self, point: &P) -> Option<f64> {
+        if self.budget.is_spent(self.stats.distance_evaluations) {
+            self.exhausted = true;
+            return None;
+        }
+        self.stats.distance_evaluations += 1;
+        Some(point.distance(self.query))
+    }
+
+    /// Returns the distance a point must beat to be among the found points.
+    fn bound(&self) -> f64 {
+        if self.found.len() < self.k {
+            f64::INFINITY
+        } else {
+            self.found[self.k - 1].1
+        }
+    }
+
+    /// Records the given point if it is among the nearest found so far.
+    fn offer(&mut self, point: &'a P, dist: f64) {
+        if dist >= self.bound() || !(self.filter)(point) { return; }
+        let index = self.found
+            .iter()
+            .position(|&(_, d)| dist < d)
+            .unwrap_or(self.found.len());
+        self.found.insert(index, (point, dist));
+        self.found.truncate(self.k);
+    }
+
+    // Pseudocode from paper:
+    // function findNearestNeighbor(Point tree p,
+    //                              query point x,
+    //                              nearest neighbor so far y)
+    //     if P(p, x) < P(y, x) then
+    //         y←p
+    //     for each child q of p sorted by distance to x do
+    //         if P(y, x) > P(x, q) − maxdist(q) then
+    //             y ← findNearestNeighbor(q, x, y)
+    //     return y
+    fn visit(&mut self, cover: &'a Cover<P>, dist: f64, depth: usize) {
+        self.stats.nodes_visited += 1;
+        self.stats.max_depth = self.stats.max_depth.max(depth);
+        self.offer(cover.point(), dist);
+
+        // Sort children by distance to query point.
+        let mut children: Vec<(&'a Cover<P>, f64)> = Vec::new();
+        for child in cover.children() {
+            match self.distance(child.point()) {
+                Some(dist) => children.push((child, dist)),
+                None => {
+                    // Out of budget: keep what has been measured and stop.
+                    for (child, dist) in children {
+                        self.offer(child.point(), dist);
+                    }
+                    return;
+                },
+            }
+        }
+        children.sort_by(|a, b| a.1
+            .partial_cmp(&b.1)
+            .expect("sort by distance to target"));
+
+        for (child, dist) in children {
+            // If closer points could be below this one, recurse. Points
+            // within the slack of the bound are not worth finding.
+            if self.bound() > (dist - child.max_distance()) * self.slack {
+                self.visit(child, dist, depth + 1);
+                if self.exhausted { return; }
+            } else {
+                self.stats.subtrees_pruned += 1;
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// FarthestSearch
+////////////////////////////////////////////////////////////////////////////////
+/// A k-farthest neighbor search over a `Cover` and its descendents.
+pub(crate) struct FarthestSearch<'a, P> where P: Point + 'a {
+    /// The query point.
+    query: P,
+    /// The number of points to find.
+    k: usize,
+    /// The farthest points found so far and their distances, farthest first.
+    found: Vec<(&'a P, f64)>,
+}
+
+impl<'a, P> FarthestSearch<'a, P> where P: Point {
+    /// Constructs a new `FarthestSearch` for the `k` farthest points from the
+    /// query.
+    pub(crate) fn new(query: P, k: usize) -> Self {
+        FarthestSearch { query, k, found: Vec::with_capacity(k) }
+    }
+
+    /// Runs the search over the given `Cover` and its descendents.
+    pub(crate) fn run(mut self, root: Option<&'a Cover<P>>)
+        -> Vec<(&'a P, f64)>
+    {
+        if self.k > 0 {
+            if let Some(root) = root {
+                let dist = root.point().distance(self.query);
+                self.visit(root, dist);
+            }
+        }
+        self.found
+    }
+
+    /// Returns the distance a point must exceed to be among the found points.
+    fn bound(&self) -> f64 {
+        if self.found.len() < self.k {
+            f64::NEG_INFINITY
+        } else {
+            self.found[self.k - 1].1
+        }
+    }
+
+    /// Records the given point if it is among the farthest found so far.
+    fn offer(&mut self, point: &'a P, dist: f64) {
+        if dist <= self.bound() { return; }
+        let index = self.found
+            .iter()
+            .position(|&(_, d)| dist > d)
+            .unwrap_or(self.found.len());
+        self.found.insert(index, (point, dist));
+        self.found.truncate(self.k);
+    }
+
+    fn visit(&mut self, cover: &'a Cover<P>, dist: f64) {
+        self.offer(cover.point(), dist);
+
+        // Sort children by distance to query point, farthest first.
+        let mut children: Vec<(&'a Cover<P>, f64)> = cover.children()
+            .iter()
+            .map(|child| (child, child.point().distance(self.query)))
+            .collect();
+        children.sort_by(|a, b| b.1
+            .partial_cmp(&a.1)
+            .expect("sort by distance to target"));
+
+        for (child, dist) in children {
+            // If farther points could be below this one, recurse.
+            if dist + child.max_distance() > self.bound() {
+                self.visit(child, dist);
+            }
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// find_within
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the points in the given `Cover` and its descendents lying within
+/// `radius` of the query, with their distances, nearest first.
+pub(crate) fn find_within<P>(root: Option<&Cover<P>>, query: P, radius: f64)
+    -> Vec<(&P, f64)>
+    where P: Point
+{
+    let mut found = Vec::new();
+    let mut stack: Vec<(&Cover<P>, f64)> = root
+        .into_iter()
+        .map(|cover| (cover, cover.point().distance(query)))
+        .collect();
+    while let Some((cover, dist)) = stack.pop() {
+        if dist <= radius {
+            found.push((cover.point(), dist));
+        }
+        for child in cover.children() {
+            // Only descend if part of the subtree could be in range.
+            let dist = child.point().distance(query);
+            if dist - child.max_distance() <= radius {
+                stack.push((child, dist));
+            }
+        }
+    }
+    found.sort_by(|a, b| a.1
+        .partial_cmp(&b.1)
+        .expect("sort by distance to target"));
+    found
+}
@@ -0,0 +1,182 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides the policy for automatically rebuilding a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+use query::Search;
+
+
+/// The number of points queried when measuring the query cost of a tree.
+const PROBES: usize = 16;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// RebuildPolicy
+////////////////////////////////////////////////////////////////////////////////
+/// When a [`CoverTree`] rebuilds itself after insertions and removals.
+///
+/// Every `check_interval` changes, the tree measures its depth and the mean
+/// number of distance calculations a nearest neighbor query on some of its
+/// points takes. If either has grown past the given factor of its value
+/// after the last rebuild, the tree is rebuilt with [`CoverTree::rebuild`].
+///
+/// [`CoverTree`]: struct.CoverTree.html
+/// [`CoverTree::rebuild`]: struct.CoverTree.html#method.rebuild
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RebuildPolicy {
+    /// The number of changes between measurements.
+    check_interval: usize,
+    /// The factor by which the depth may grow.
+    max_depth_growth: Option<f64>,
+    /// The factor by which the mean query cost may grow.
+    max_cost_growth: Option<f64>,
+}
+
+impl RebuildPolicy {
+    /// Constructs a `RebuildPolicy` measuring the tree after every
+    /// `check_interval` insertions and removals. It never rebuilds until a
+    /// growth limit is given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `check_interval` is zero.
+    pub fn every(check_interval: usize) -> Self {
+        assert!(check_interval > 0, "rebuild check interval must be positive");
+        RebuildPolicy {
+            check_interval,
+            max_depth_growth: None,
+            max_cost_growth: None,
+        }
+    }
+
+    /// Rebuilds once the depth of the tree grows past the given factor of its
+    /// depth after the last rebuild.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is less than one or NaN.
+    pub fn with_max_depth_growth(mut self, factor: f64) -> Self {
+        assert!(factor >= 1.0, "rebuild growth factor must be at least one");
+        self.max_depth_growth = Some(factor);
+        self
+    }
+
+    /// Rebuilds once the mean query cost grows past the given factor of its
+    /// cost after the last rebuild.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is less than one or NaN.
+    pub fn with_max_cost_growth(mut self, factor: f64) -> Self {
+        assert!(factor >= 1.0, "rebuild growth factor must be at least one");
+        self.max_cost_growth = Some(factor);
+        self
+    }
+
+    /// Returns the number of changes between measurements.
+    pub fn check_interval(&self) -> usize {
+        self.check_interval
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Shape
+////////////////////////////////////////////////////////////////////////////////
+/// The measurements of a tree compared by a `RebuildPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Shape {
+    /// The depth of the deepest node, with the root at depth zero.
+    depth: usize,
+    /// The mean number of distance calculations of a nearest neighbor query.
+    query_cost: f64,
+}
+
+impl Shape {
+    /// Measures the tree below the given root, or returns `None` if it is
+    /// empty. The queries are for points spread evenly through the tree,
+    /// found without walking all of it.
+    pub(crate) fn measure<P>(root: Option<&Cover<P>>) -> Option<Self>
+        where P: Point
+    {
+        let root = root?;
+        let count = root.size().min(PROBES);
+        let step = root.size() / count;
+        let evaluations: usize = (0..count)
+            .map(|i| *root.nth_point(i * step))
+            .map(|probe| Search::new(probe, 1).run(Some(root)).1)
+            .map(|stats| stats.distance_evaluations)
+            .sum();
+        Some(Shape {
+            depth: root.height(),
+            query_cost: evaluations as f64 / count as f64,
+        })
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Drift
+////////////////////////////////////////////////////////////////////////////////
+/// Tracks the changes to a tree under a `RebuildPolicy`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Drift {
+    /// The policy being applied.
+    policy: RebuildPolicy,
+    /// The number of changes since the last measurement.
+    changes: usize,
+    /// The measurements after the last rebuild, or `None` if the tree has
+    /// not been measured since it was empty.
+    baseline: Option<Shape>,
+}
+
+impl Drift {
+    /// Constructs a new `Drift` for the given policy, with the given
+    /// measurements of the tree.
+    pub(crate) fn new(policy: RebuildPolicy, baseline: Option<Shape>) -> Self {
+        Drift { policy, changes: 0, baseline }
+    }
+
+    /// Returns the policy being applied.
+    pub(crate) fn policy(&self) -> RebuildPolicy {
+        self.policy
+    }
+
+    /// Counts a change, returning `true` if the tree is due to be measured.
+    pub(crate) fn note_change(&mut self) -> bool {
+        self.changes += 1;
+        if self.changes < self.policy.check_interval { return false; }
+        self.changes = 0;
+        true
+    }
+
+    /// Returns `true` if the given measurements have grown past the limits of
+    /// the policy. Without a baseline, the measurements become the baseline.
+    pub(crate) fn has_drifted(&mut self, shape: Option<Shape>) -> bool {
+        let (baseline, shape) = match (self.baseline, shape) {
+            (Some(baseline), Some(shape)) => (baseline, shape),
+            _ => {
+                self.baseline = shape;
+                return false;
+            },
+        };
+        let deeper = self.policy.max_depth_growth
+            .is_some_and(|f| shape.depth as f64 > f * baseline.depth as f64);
+        let slower = self.policy.max_cost_growth
+            .is_some_and(|f| shape.query_cost > f * baseline.query_cost);
+        deeper || slower
+    }
+}
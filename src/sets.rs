@@ -0,0 +1,90 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides distances between the point sets of two `CoverTree`s.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use cover::Cover;
+use iter::Iter;
+
+
+////////////////////////////////////////////////////////////////////////////////
+// directed_hausdorff
+////////////////////////////////////////////////////////////////////////////////
+/// Returns the largest distance from a point of the query points to its
+/// nearest reference point.
+///
+/// Once some query point is known to be that far from the reference points,
+/// the search for each later query point stops as soon as it finds a
+/// reference point no farther away, since that point cannot raise the
+/// maximum.
+pub(crate) fn directed_hausdorff<P>(queries: Iter<P>, reference: Option<&Cover<P>>)
+    -> f64
+    where P: Point
+{
+    let mut farthest = 0.0;
+    for &query in queries {
+        let root = match reference {
+            Some(root) => root,
+            None       => return f64::INFINITY,
+        };
+        if let Some(dist) = nearest_beyond(root, query, farthest) {
+            farthest = dist;
+        }
+    }
+    farthest
+}
+
+/// Returns the distance from the query to the nearest point below the given
+/// `Cover`, or `None` as soon as a point within `threshold` is found.
+///
+/// Subtrees that could hold a point within the threshold are searched first.
+/// The others are only searched for the nearest distance once no such point
+/// is left to find.
+fn nearest_beyond<P>(root: &Cover<P>, query: P, threshold: f64) -> Option<f64>
+    where P: Point
+{
+    let root_dist = root.point().distance(query);
+    if root_dist <= threshold { return None; }
+
+    let mut nearest = root_dist;
+    let mut within = vec![(root, root_dist)];
+    let mut beyond = Vec::new();
+    while let Some((cover, dist)) = within.pop().or_else(|| beyond.pop()) {
+        // Skip subtrees that cannot hold a point nearer than those found
+        // since they were reached.
+        if dist - cover.max_distance() >= nearest { continue; }
+
+        let mut children = Vec::with_capacity(cover.children().len());
+        for child in cover.children() {
+            let dist = child.point().distance(query);
+            if dist <= threshold { return None; }
+            nearest = nearest.min(dist);
+            children.push((child, dist));
+        }
+
+        // Visit the nearest children first.
+        children.sort_by(|a, b| b.1
+            .partial_cmp(&a.1)
+            .expect("sort by distance to target"));
+        for (child, dist) in children {
+            if dist - child.max_distance() <= threshold {
+                within.push((child, dist));
+            } else {
+                beyond.push((child, dist));
+            }
+        }
+    }
+    Some(nearest)
+}
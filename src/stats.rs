@@ -0,0 +1,145 @@
+// Copyright 2018 Skylor R. Schermer.
+// Copyright 2025 phyalex.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+////////////////////////////////////////////////////////////////////////////////
+//!
+//! Provides structural statistics of a `CoverTree`.
+//!
+////////////////////////////////////////////////////////////////////////////////
+
+
+use Point;
+use node::{CoverNode, DepthFirst, Levels};
+
+
+////////////////////////////////////////////////////////////////////////////////
+// Summary
+////////////////////////////////////////////////////////////////////////////////
+/// Summary statistics of a collection of values.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Summary {
+    /// The number of values.
+    pub count: usize,
+    /// The smallest value.
+    pub min: f64,
+    /// The arithmetic mean of the values.
+    pub mean: f64,
+    /// The median of the values.
+    pub median: f64,
+    /// The largest value.
+    pub max: f64,
+}
+
+impl Summary {
+    /// Constructs a `Summary` of the given values. All fields are zero if
+    /// there are no values.
+    pub(crate) fn new(mut values: Vec<f64>) -> Self {
+        if values.is_empty() { return Summary::default(); }
+
+        values.sort_by(|a, b| a.partial_cmp(b).expect("sort summary values"));
+        let count = values.len();
+        let median = if count % 2 == 0 {
+            (values[count / 2 - 1] + values[count / 2]) / 2.0
+        } else {
+            values[count / 2]
+        };
+
+        Summary {
+            count,
+            min: values[0],
+            mean: values.iter().sum::<f64>() / count as f64,
+            median,
+            max: values[count - 1],
+        }
+    }
+}
+
+
+////////////////////////////////////////////////////////////////////////////////
+// TreeStats
+////////////////////////////////////////////////////////////////////////////////
+/// A report on the shape of a [`CoverTree`], returned by
+/// [`CoverTree::stats`].
+///
+/// [`CoverTree`]: struct.CoverTree.html
+/// [`CoverTree::stats`]: struct.CoverTree.html#method.stats
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStats {
+    /// The number of nodes in the tree.
+    pub node_count: usize,
+    /// The number of edges on the longest path from the root to a leaf.
+    pub depth: usize,
+    /// The number of distinct levels used by the nodes.
+    pub level_count: usize,
+    /// The number of nodes at each level, from the highest level to the
+    /// lowest.
+    pub nodes_per_level: Vec<(usize, usize)>,
+    /// The number of nodes without children.
+    pub leaf_count: usize,
+    /// The number of children of each node that has children.
+    pub fan_out: Summary,
+    /// The number of nodes on the longest path in which every node has a
+    /// single child. Long chains come from duplicate or tightly clustered
+    /// points.
+    pub longest_chain: usize,
+    /// The ratio of `max_distance` to `cover_distance` for each node that has
+    /// children. Ratios well below one mean the covering radii are loose for
+    /// the data, ratios above one mean the covering invariant is violated.
+    pub distance_ratio: Summary,
+}
+
+impl TreeStats {
+    /// Collects the statistics of the tree below the given root.
+    pub(crate) fn new<P>(root: Option<CoverNode<P>>) -> Self where P: Point {
+        let mut stats = TreeStats {
+            node_count: 0,
+            depth: 0,
+            level_count: 0,
+            nodes_per_level: Vec::new(),
+            leaf_count: 0,
+            fan_out: Summary::default(),
+            longest_chain: root.map_or(0, longest_chain),
+            distance_ratio: Summary::default(),
+        };
+
+        let mut fan_out = Vec::new();
+        let mut distance_ratio = Vec::new();
+        for node in DepthFirst::new(root) {
+            stats.node_count += 1;
+            stats.depth = stats.depth.max(node.depth());
+            if node.is_leaf() {
+                stats.leaf_count += 1;
+            } else {
+                fan_out.push(node.children().len() as f64);
+                distance_ratio.push(node.max_distance() / node.cover_distance());
+            }
+        }
+        stats.fan_out = Summary::new(fan_out);
+        stats.distance_ratio = Summary::new(distance_ratio);
+
+        stats.nodes_per_level = Levels::new(root)
+            .map(|(level, nodes)| (level, nodes.len()))
+            .collect();
+        stats.level_count = stats.nodes_per_level.len();
+        stats
+    }
+}
+
+/// Returns the number of nodes on the longest single-child chain starting at
+/// or below the given node.
+fn longest_chain<P>(node: CoverNode<P>) -> usize where P: Point {
+    let mut longest = 0;
+    let mut stack = vec![(node, 0)];
+    while let Some((node, run)) = stack.pop() {
+        let run = if node.children().len() == 1 { run + 1 } else { 0 };
+        longest = longest.max(run);
+        stack.extend(node.children().map(|child| (child, run)));
+    }
+    longest
+}
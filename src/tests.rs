@@ -64,7 +64,7 @@ fn manual_insert() {
 
 #[test]
 fn nearest_neighbor_f64() {
-    let mut ct: CoverTree<f64> = CoverTree::from_items(test_f64_data().into_iter());
+    let ct: CoverTree<f64> = CoverTree::from_items(test_f64_data().into_iter());
 
     assert_eq!(ct.find_nearest(0.0).unwrap(), &1.0);
     assert_eq!(ct.find_nearest(2.0).unwrap(), &1.0);
@@ -79,7 +79,1112 @@ fn nearest_neighbor_f64() {
 
 #[test]
 fn nearest_neighbor_i64() {
-    let mut ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
 
     assert_eq!(ct.find_nearest(0).unwrap(), &0);
-}
\ No newline at end of file
+}
+
+#[test]
+fn iter_yields_all_points() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    let mut points: Vec<i64> = ct.iter().cloned().collect();
+    points.sort();
+    let mut data = test_i64_data();
+    data.sort();
+
+    assert_eq!(ct.iter().len(), ct.len());
+    assert_eq!(points, data);
+}
+
+#[test]
+fn into_iter_yields_all_points() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    let mut borrowed: Vec<i64> = (&ct).into_iter().cloned().collect();
+    let mut owned: Vec<i64> = ct.into_iter().collect();
+    borrowed.sort();
+    owned.sort();
+    let mut data = test_i64_data();
+    data.sort();
+
+    assert_eq!(borrowed, data);
+    assert_eq!(owned, data);
+}
+
+#[test]
+fn iter_empty_tree() {
+    let ct: CoverTree<f64> = CoverTree::new();
+    assert_eq!(ct.iter().next(), None);
+    assert_eq!(ct.into_iter().next(), None);
+}
+
+#[test]
+fn collect_and_extend() {
+    let mut ct: CoverTree<i64> = test_i64_data().into_iter().collect();
+    assert_eq!(ct.len(), test_i64_data().len());
+
+    ct.extend(vec![-1000, 1000]);
+    ct.extend(&[20000]);
+    assert_eq!(ct.len(), test_i64_data().len() + 3);
+    assert!(ct.contains(&20000));
+}
+
+#[test]
+fn contains_every_point() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    for point in test_i64_data() {
+        assert!(ct.contains(&point));
+        assert!(!ct.contains(&(point + 100_000)));
+    }
+}
+
+#[test]
+fn remove_keeps_other_points() {
+    let mut ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let mut data = test_i64_data();
+
+    // Remove the root while it still has descendents.
+    let root = *ct.root().unwrap().point();
+    assert!(!ct.root().unwrap().is_leaf());
+    let index = data.iter().position(|&p| p == root).unwrap();
+    assert_eq!(ct.remove(data.remove(index)), Some(root));
+    assert_eq!(ct.len(), data.len());
+
+    // Remove from the front so that inner Covers are removed.
+    data.reverse();
+    while let Some(point) = data.pop() {
+        assert_eq!(ct.remove(point), Some(point));
+        assert_eq!(ct.len(), data.len());
+        assert_eq!(ct.iter().count(), data.len());
+        for rest in &data {
+            assert!(ct.contains(rest));
+        }
+        if !data.is_empty() {
+            assert_eq!(
+                ct.find_nearest(point).map(|p| (p - point).abs()),
+                data.iter().map(|p| (p - point).abs()).min());
+        }
+    }
+    assert!(ct.is_empty());
+    assert_eq!(ct.remove(5), None);
+}
+
+#[test]
+fn retain_and_drain() {
+    let mut ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    ct.retain(|p| p % 2 == 0);
+    let evens = test_i64_data().into_iter().filter(|p| p % 2 == 0).count();
+    assert_eq!(ct.len(), evens);
+    assert_eq!(ct.iter().count(), evens);
+    assert!(ct.iter().all(|p| p % 2 == 0));
+
+    let mut drained: Vec<i64> = ct.drain().collect();
+    drained.sort();
+    let mut expected: Vec<i64> = test_i64_data()
+        .into_iter()
+        .filter(|p| p % 2 == 0)
+        .collect();
+    expected.sort();
+    assert_eq!(drained, expected);
+    assert!(ct.is_empty());
+    assert_eq!(ct.span_factor(), ::DEFAULT_SPAN_FACTOR);
+}
+
+#[test]
+fn traversals_visit_every_node() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    assert_eq!(ct.breadth_first().count(), ct.len());
+    assert_eq!(ct.depth_first().count(), ct.len());
+
+    let mut dfs: Vec<i64> = ct.depth_first().map(|n| *n.point()).collect();
+    let mut bfs: Vec<i64> = ct.breadth_first().map(|n| *n.point()).collect();
+    dfs.sort();
+    bfs.sort();
+    assert_eq!(dfs, bfs);
+
+    let levels: Vec<usize> = ct.levels().map(|(level, _)| level).collect();
+    assert!(levels.windows(2).all(|w| w[0] > w[1]));
+    assert_eq!(ct.levels().map(|(_, nodes)| nodes.len()).sum::<usize>(), ct.len());
+}
+
+#[test]
+fn max_distance_matches_descendents() {
+    let mut ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    ct.remove(910);
+    ct.remove(18);
+    // Points far from the root promote leaves from deep in the tree.
+    ct.extend((0..300).map(|i| (i * 7919 % 10007) * (i % 7 + 1) - 20000));
+
+    fn check(node: ::CoverNode<i64>) -> Vec<i64> {
+        let mut below = Vec::new();
+        for child in node.children() {
+            below.push(*child.point());
+            below.extend(check(child));
+        }
+        let farthest = below
+            .iter()
+            .map(|point| (point - node.point()).abs())
+            .max()
+            .unwrap_or(0);
+        assert_eq!(node.max_distance(), farthest as f64);
+        below
+    }
+    check(ct.root().unwrap());
+}
+
+#[test]
+fn sizes_and_heights_count_descendents() {
+    let mut ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    ct.extend((0..200).map(|i| i * 50));
+    ct.remove(910);
+    ct.remove(18);
+    let root = *ct.root().unwrap().point();
+    ct.remove(root);
+
+    fn check(node: ::CoverNode<i64>) -> usize {
+        let size = 1 + node.children().map(check).sum::<usize>();
+        assert_eq!(node.size(), size);
+        let height = node.children().map(|c| c.height() + 1).max().unwrap_or(0);
+        assert_eq!(node.height(), height);
+        size
+    }
+    assert_eq!(check(ct.root().unwrap()), ct.len());
+    assert_eq!(ct.root().unwrap().height(), ct.stats().depth);
+}
+
+#[test]
+fn stats_match_structure() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let stats = ct.stats();
+
+    assert_eq!(stats.node_count, ct.len());
+    assert_eq!(stats.level_count, ct.levels().count());
+    assert_eq!(stats.nodes_per_level.iter().map(|&(_, n)| n).sum::<usize>(), ct.len());
+    assert_eq!(stats.leaf_count + stats.fan_out.count, ct.len());
+    assert_eq!((stats.fan_out.mean * stats.fan_out.count as f64).round(), (ct.len() - 1) as f64);
+    assert_eq!(stats.depth, ct.depth_first().map(|n| n.depth()).max().unwrap());
+}
+
+#[test]
+fn stats_find_duplicate_chains() {
+    let ct: CoverTree<f64> = CoverTree::from_items(vec![5.0; 6].into_iter());
+    let stats = ct.stats();
+
+    assert_eq!(stats.longest_chain, 5);
+    assert_eq!(stats.depth, 5);
+    assert_eq!(stats.distance_ratio.max, 0.0);
+
+    let empty: CoverTree<f64> = CoverTree::new();
+    assert_eq!(empty.stats().node_count, 0);
+}
+
+#[test]
+fn dot_export_lists_nodes_and_edges() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    let dot = ct.to_dot(None);
+    assert_eq!(dot.matches("[label=").count(), ct.len());
+    assert_eq!(dot.matches(" -> ").count(), ct.len() - 1);
+    for point in test_i64_data() {
+        assert!(dot.contains(&format!("\"{}\\nlevel", point)));
+    }
+
+    let shallow = ct.to_dot(Some(1));
+    let near_root = ct.breadth_first().take_while(|n| n.depth() <= 1).count();
+    assert_eq!(shallow.matches("[label=").count(), near_root);
+    assert!(shallow.contains("hidden)"));
+}
+
+#[test]
+fn nearest_with_stats_matches_brute_force() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    let mut total = ::QueryStats::default();
+    for query in -200..9000 {
+        let (nearest, stats) = ct.find_nearest_with_stats(query);
+        assert_eq!(
+            (nearest.unwrap() - query).abs(),
+            test_i64_data().iter().map(|p| (p - query).abs()).min().unwrap());
+        assert!(stats.nodes_visited <= ct.len());
+        assert!(stats.distance_evaluations <= ct.len());
+        total += stats;
+    }
+    assert_eq!(total.queries, 9200);
+    assert!(total.subtrees_pruned > 0);
+}
+
+#[test]
+fn distance_counter_agrees_with_stats() {
+    let counter = ::DistanceCounter::new();
+    let ct: CoverTree<_> = test_i64_data()
+        .into_iter()
+        .map(|p| counter.wrap(p))
+        .collect();
+
+    counter.reset();
+    let (nearest, stats) = ct.find_nearest_with_stats(counter.wrap(700));
+    assert_eq!(nearest.unwrap().point, 699);
+    assert_eq!(counter.count(), stats.distance_evaluations);
+
+    // The stats are what a plain query costs.
+    counter.reset();
+    assert_eq!(ct.find_nearest(counter.wrap(700)).unwrap().point, 699);
+    assert_eq!(counter.count(), stats.distance_evaluations);
+}
+
+#[test]
+fn approximate_nearest_within_epsilon() {
+    let data: Vec<f64> = (0..500)
+        .map(|i| ((i * 7919) % 1000) as f64 * 0.37 + (i % 13) as f64)
+        .collect();
+    let ct: CoverTree<f64> = CoverTree::from_items(data.iter().cloned());
+
+    for &epsilon in &[0.0, 0.1, 0.5, 2.0] {
+        for i in 0..400 {
+            let query = i as f64 * 0.95 - 10.0;
+            let exact = data.iter()
+                .map(|p| (p - query).abs())
+                .fold(f64::INFINITY, f64::min);
+            let approx = ct.find_nearest_approx(query, epsilon).unwrap();
+            assert!((approx - query).abs() <= exact * (1.0 + epsilon));
+        }
+    }
+}
+
+#[test]
+fn k_nearest_matches_brute_force() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    for query in (-300..9000).step_by(37) {
+        for &k in &[1, 3, 10, 100] {
+            let found: Vec<f64> = ct.find_k_nearest(query, k)
+                .into_iter()
+                .map(|(_, d)| d)
+                .collect();
+            let mut expected: Vec<f64> = test_i64_data()
+                .into_iter()
+                .map(|p| (p - query).abs() as f64)
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            expected.truncate(k);
+            assert_eq!(found, expected);
+        }
+    }
+}
+
+#[test]
+fn budgeted_search_reports_exhaustion() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    let full = ct.find_k_nearest_budgeted(700, 3, ::Budget::unlimited());
+    assert!(full.exhaustive);
+    assert_eq!(full.value, ct.find_k_nearest(700, 3));
+
+    for max in 0..full.stats.distance_evaluations {
+        let partial = ct.find_k_nearest_budgeted(700, 3, ::Budget::evaluations(max));
+        assert!(!partial.exhaustive);
+        assert!(partial.stats.distance_evaluations <= max);
+        assert!(partial.value.len() <= max);
+    }
+
+    let exact = ::Budget::evaluations(full.stats.distance_evaluations);
+    assert!(ct.find_k_nearest_budgeted(700, 3, exact).exhaustive);
+
+    let past = ::Budget::deadline(::std::time::Instant::now());
+    let late = ct.find_nearest_budgeted(700, past);
+    assert!(!late.exhaustive);
+    assert_eq!(late.value, None);
+}
+
+#[test]
+fn filtered_nearest_matches_brute_force() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    for query in (-300..9000).step_by(53) {
+        let found: Vec<f64> = ct.find_k_nearest_where(query, 4, |p| p % 3 == 0)
+            .into_iter()
+            .map(|(point, d)| {
+                assert_eq!(point % 3, 0);
+                d
+            })
+            .collect();
+        let mut expected: Vec<f64> = test_i64_data()
+            .into_iter()
+            .filter(|p| p % 3 == 0)
+            .map(|p| (p - query).abs() as f64)
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        expected.truncate(4);
+        assert_eq!(found, expected);
+    }
+
+    assert_eq!(ct.find_nearest_where(0, |_| false), None);
+}
+
+#[test]
+fn k_farthest_matches_brute_force() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    for query in (-3000..12000).step_by(97) {
+        let found: Vec<f64> = ct.find_k_farthest(query, 5)
+            .into_iter()
+            .map(|(_, d)| d)
+            .collect();
+        let mut expected: Vec<f64> = test_i64_data()
+            .into_iter()
+            .map(|p| (p - query).abs() as f64)
+            .collect();
+        expected.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        expected.truncate(5);
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn find_within_matches_brute_force() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+
+    for query in (-300..9000).step_by(41) {
+        for &radius in &[0.0, 10.0, 150.0, 2000.0] {
+            let mut found: Vec<i64> = ct.find_within(query, radius)
+                .into_iter()
+                .map(|(p, _)| *p)
+                .collect();
+            let mut expected: Vec<i64> = test_i64_data()
+                .into_iter()
+                .filter(|p| (p - query).abs() as f64 <= radius)
+                .collect();
+            found.sort();
+            expected.sort();
+            assert_eq!(found, expected);
+        }
+    }
+}
+
+#[test]
+fn self_join_reports_each_pair_once() {
+    let mut data = test_i64_data();
+    data.push(512);
+    let ct: CoverTree<i64> = CoverTree::from_items(data.iter().cloned());
+
+    for &radius in &[0.0, 5.0, 40.0, 300.0, 20000.0] {
+        let mut found: Vec<(i64, i64)> = ct.self_join(radius)
+            .into_iter()
+            .map(|(a, b, _)| (*a.min(b), *a.max(b)))
+            .collect();
+        let mut expected = Vec::new();
+        for (i, a) in data.iter().enumerate() {
+            for b in &data[i + 1..] {
+                if (a - b).abs() as f64 <= radius {
+                    expected.push((*a.min(b), *a.max(b)));
+                }
+            }
+        }
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+}
+
+fn test_i64_other_data() -> Vec<i64> {
+    (0..80).map(|i| (i * 7919) % 9000 - 400).collect()
+}
+
+#[test]
+fn join_within_matches_brute_force() {
+    let a: CoverTree<i64> = test_i64_data().into_iter().collect();
+    let b: CoverTree<i64> = test_i64_other_data().into_iter().collect();
+
+    for &radius in &[0.0, 3.0, 50.0, 700.0] {
+        let mut found: Vec<(i64, i64)> = a.join_within(&b, radius)
+            .into_iter()
+            .map(|(p, q, _)| (*p, *q))
+            .collect();
+        let mut expected = Vec::new();
+        for p in test_i64_data() {
+            for q in test_i64_other_data() {
+                if (p - q).abs() as f64 <= radius { expected.push((p, q)); }
+            }
+        }
+        found.sort();
+        expected.sort();
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn join_nearest_matches_brute_force() {
+    let a: CoverTree<i64> = test_i64_data().into_iter().collect();
+    let b: CoverTree<i64> = test_i64_other_data().into_iter().collect();
+
+    let found = a.join_nearest(&b);
+    assert_eq!(found.len(), a.len());
+    for (p, q, dist) in found {
+        let nearest = test_i64_other_data()
+            .into_iter()
+            .map(|q| (p - q).abs())
+            .min()
+            .unwrap();
+        assert_eq!((p - q).abs(), nearest);
+        assert_eq!(dist, nearest as f64);
+    }
+
+    assert!(a.join_nearest(&CoverTree::new()).is_empty());
+}
+
+#[test]
+fn knn_graph_matches_brute_force() {
+    let mut data = test_i64_data();
+    data.push(512);
+    let ct: CoverTree<i64> = data.iter().cloned().collect();
+
+    for &k in &[1, 4, 100] {
+        let graph = ct.knn_graph(k);
+        assert_eq!(graph.len(), ct.len());
+        assert_eq!(ct.knn_graph_parallel(k, 3), graph);
+
+        let points: Vec<&i64> = ct.iter().collect();
+        for (i, (point, neighbors)) in graph.into_iter().enumerate() {
+            assert!(::std::ptr::eq(point, points[i]));
+            assert!(neighbors.iter().all(|&(n, _)| !::std::ptr::eq(n, point)));
+
+            let found: Vec<f64> = neighbors.into_iter().map(|(_, d)| d).collect();
+            let mut expected: Vec<f64> = points
+                .iter()
+                .filter(|&&other| !::std::ptr::eq(other, point))
+                .map(|other| (*other - point).abs() as f64)
+                .collect();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            expected.truncate(k);
+            assert_eq!(found, expected);
+        }
+    }
+}
+
+#[test]
+fn dbscan_matches_brute_force_partition() {
+    use cluster::{self, Label};
+
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let (eps, min_pts) = (40.0, 3);
+    let labels = cluster::dbscan(&ct, eps, min_pts);
+    assert_eq!(labels.len(), ct.len());
+
+    let neighbors = |p: i64| test_i64_data()
+        .into_iter()
+        .filter(|q| (p - q).abs() as f64 <= eps)
+        .count();
+    let is_core = |p: i64| neighbors(p) >= min_pts;
+
+    for &(p, label) in &labels {
+        let near_core = test_i64_data()
+            .into_iter()
+            .any(|q| is_core(q) && (p - q).abs() as f64 <= eps);
+        assert_eq!(label == Label::Noise, !near_core);
+    }
+    // Core points share a cluster exactly when they are chained by cores.
+    for &(p, a) in &labels {
+        for &(q, b) in &labels {
+            if is_core(*p) && is_core(*q) && (p - q).abs() as f64 <= eps {
+                assert_eq!(a, b);
+            }
+        }
+    }
+}
+
+/// Returns the total weight of a minimum spanning tree over the given points,
+/// found by Prim's algorithm.
+fn brute_force_mst_weight(points: &[i64]) -> f64 {
+    let mut dist = vec![f64::INFINITY; points.len()];
+    let mut done = vec![false; points.len()];
+    let mut total = 0.0;
+    dist[0] = 0.0;
+    for _ in 0..points.len() {
+        let next = (0..points.len())
+            .filter(|&i| !done[i])
+            .min_by(|&a, &b| dist[a].partial_cmp(&dist[b]).unwrap())
+            .unwrap();
+        done[next] = true;
+        total += dist[next];
+        for i in 0..points.len() {
+            let d = (points[i] - points[next]).abs() as f64;
+            if !done[i] && d < dist[i] { dist[i] = d; }
+        }
+    }
+    total
+}
+
+#[test]
+fn minimum_spanning_tree_matches_brute_force() {
+    for data in &[test_i64_data(), test_i64_other_data()] {
+        let ct: CoverTree<i64> = data.iter().cloned().collect();
+        let edges = ct.minimum_spanning_tree();
+        assert_eq!(edges.len(), data.len() - 1);
+
+        let total: f64 = edges.iter().map(|e| e.2).sum();
+        assert_eq!(total, brute_force_mst_weight(data));
+        for w in edges.windows(2) {
+            assert!(w[0].2 <= w[1].2);
+        }
+        for &(a, b, dist) in &edges {
+            assert_eq!((a - b).abs() as f64, dist);
+        }
+    }
+}
+
+#[test]
+fn single_linkage_cut_matches_gaps() {
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let dendrogram = ct.single_linkage();
+    assert_eq!(dendrogram.merges().len(), ct.len() - 1);
+    assert_eq!(dendrogram.merges().last().unwrap().size, ct.len());
+
+    // In one dimension, single-linkage clusters are the runs of sorted points
+    // without a gap wider than the threshold.
+    let mut sorted = test_i64_data();
+    sorted.sort();
+    for &threshold in &[5.0, 40.0, 300.0] {
+        let labels = dendrogram.cut_at_distance(threshold);
+        let label = |x: i64| labels.iter().find(|l| *l.0 == x).unwrap().1;
+        for w in sorted.windows(2) {
+            let same = (w[1] - w[0]) as f64 <= threshold;
+            assert_eq!(label(w[0]) == label(w[1]), same);
+        }
+
+        let clusters = sorted
+            .windows(2)
+            .filter(|w| (w[1] - w[0]) as f64 > threshold)
+            .count() + 1;
+        assert_eq!(dendrogram.cut_into(clusters), labels);
+    }
+}
+
+#[test]
+fn hdbscan_finds_separated_groups() {
+    use cluster::{self, Label, Member};
+
+    // Three groups of different density, far apart, plus scattered points.
+    let mut data: Vec<i64> = (0..30).map(|i| i * 2).collect();
+    data.extend((0..25).map(|i| 1000 + i * 5));
+    data.extend((0..20).map(|i| 5000 + i));
+    data.extend(&[500, 2500, 3700, 9000]);
+    let ct: CoverTree<i64> = data.iter().cloned().collect();
+
+    let result = cluster::hdbscan(&ct, 5, 3);
+    assert_eq!(result.labels.len(), data.len());
+    assert_eq!(result.selected.len(), 3);
+
+    // Scattered points falling out of a selected cluster still belong to it,
+    // with a low probability. Points falling out of the root are noise.
+    let position = |x: i64| result.labels.iter().position(|l| *l.0 == x).unwrap();
+    let label_of = |x: i64| result.labels[position(x)].1;
+    let prob_of = |x: i64| result.probabilities[position(x)];
+    for &(p, label) in &result.labels {
+        let expected = match *p {
+            0 ..= 500     => label_of(0),
+            1000 ..= 1120 => label_of(1000),
+            3700 ..= 5019 => label_of(5000),
+            _             => Label::Noise,
+        };
+        assert_eq!(label, expected);
+    }
+    assert!(label_of(0) != label_of(1000) && label_of(1000) != label_of(5000));
+    assert_eq!(label_of(2500), Label::Noise);
+    assert!(prob_of(500) < prob_of(20));
+    assert!(prob_of(3700) < prob_of(5010));
+
+    for (&(_, label), &prob) in result.labels.iter().zip(&result.probabilities) {
+        assert!((0.0..=1.0).contains(&prob));
+        assert_eq!(label == Label::Noise, prob == 0.0);
+    }
+
+    // Every point leaves the condensed tree exactly once, and each cluster's
+    // children account for all of its points.
+    let mut seen = vec![0; data.len()];
+    let mut sizes = ::std::collections::HashMap::new();
+    for edge in &result.condensed_tree {
+        *sizes.entry(edge.parent).or_insert(0) += edge.size;
+        if let Member::Point(i) = edge.child { seen[i] += 1; }
+    }
+    assert!(seen.iter().all(|&count| count == 1));
+    assert_eq!(sizes[&0], data.len());
+    for edge in &result.condensed_tree {
+        if let Member::Cluster(id) = edge.child {
+            assert_eq!(sizes[&id], edge.size);
+        }
+    }
+}
+
+#[test]
+fn hdbscan_handles_duplicate_points() {
+    use cluster::{self, Label};
+
+    // Duplicates merge at distance zero in both groups.
+    let mut data: Vec<i64> = vec![0; 6];
+    data.extend((1..12).map(|i| i * 3));
+    data.extend(&[1000; 6]);
+    data.extend((1..12).map(|i| 1000 + i * 4));
+    let ct: CoverTree<i64> = data.iter().cloned().collect();
+
+    let result = cluster::hdbscan(&ct, 4, 1);
+    assert_eq!(result.selected.len(), 2);
+    assert!(result.condensed_tree.iter().all(|edge| edge.lambda.is_finite()));
+
+    let label_of = |x: i64| result.labels.iter().find(|l| *l.0 == x).unwrap().1;
+    assert_ne!(label_of(0), label_of(1000));
+    for (&(p, label), &prob) in result.labels.iter().zip(&result.probabilities) {
+        let expected = if *p < 1000 { label_of(0) } else { label_of(1000) };
+        assert_eq!(label, expected);
+        assert_ne!(label, Label::Noise);
+        assert!(prob > 0.0 && prob <= 1.0);
+    }
+}
+
+#[test]
+fn farthest_first_order_matches_brute_force() {
+    let ct: CoverTree<i64> = test_i64_other_data().into_iter().collect();
+    let points: Vec<i64> = ct.iter().cloned().collect();
+
+    // Gonzalez's algorithm, breaking ties by iteration order.
+    let mut nearest = vec![f64::INFINITY; points.len()];
+    let mut chosen = vec![false; points.len()];
+    let mut expected = Vec::new();
+    let mut next = 0;
+    for _ in 0..points.len() {
+        expected.push((points[next], nearest[next]));
+        chosen[next] = true;
+        for i in 0..points.len() {
+            let d = (points[i] - points[next]).abs() as f64;
+            nearest[i] = nearest[i].min(d);
+        }
+        next = (0..points.len())
+            .filter(|&i| !chosen[i])
+            .fold(None, |best: Option<usize>, i| match best {
+                Some(b) if nearest[b] >= nearest[i] => Some(b),
+                _ => Some(i),
+            })
+            .unwrap_or(0);
+    }
+
+    let order: Vec<(i64, f64)> = ct.farthest_first_order()
+        .into_iter()
+        .map(|(&p, r)| (p, r))
+        .collect();
+    assert_eq!(order, expected);
+
+    let centers: Vec<i64> = ct.k_centers(7).into_iter().cloned().collect();
+    let firsts: Vec<i64> = expected.iter().take(7).map(|e| e.0).collect();
+    assert_eq!(centers, firsts);
+    assert_eq!(ct.k_centers(500).len(), ct.len());
+}
+
+#[test]
+fn kmeans_plus_plus_picks_distinct_points() {
+    use cluster;
+
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    for seed in 0..10 {
+        let centers = cluster::kmeans_plus_plus(&ct, 6, seed);
+        assert_eq!(centers.len(), 6);
+        for (i, a) in centers.iter().enumerate() {
+            assert!(centers[i + 1..].iter().all(|b| a != b));
+        }
+    }
+
+    // Only three distinct points can be chosen.
+    let ct: CoverTree<i64> = vec![1, 1, 5, 5, 5, 9].into_iter().collect();
+    assert_eq!(cluster::kmeans_plus_plus(&ct, 5, 3).len(), 3);
+}
+
+#[test]
+fn k_medoids_assigns_nearest_medoid() {
+    use cluster::{self, Label};
+
+    let ct: CoverTree<i64> = CoverTree::from_items(test_i64_data().into_iter());
+    let result = cluster::k_medoids(&ct, 4, 50, 11);
+    assert_eq!(result.medoids.len(), 4);
+    assert_eq!(result.labels.len(), ct.len());
+
+    let mut cost = 0.0;
+    for &(p, label) in &result.labels {
+        let best = result.medoids
+            .iter()
+            .map(|m| (p - *m).abs())
+            .min()
+            .unwrap();
+        match label {
+            Label::Cluster(c) => assert_eq!((p - result.medoids[c]).abs(), best),
+            Label::Noise      => panic!("k-medoids labels every point"),
+        }
+        cost += best as f64;
+    }
+    assert_eq!(result.cost, cost);
+
+    // Well separated groups are found exactly.
+    let ct: CoverTree<i64> = vec![0, 1, 2, 100, 101, 102, 500, 501, 502]
+        .into_iter()
+        .collect();
+    let result = cluster::k_medoids(&ct, 3, 100, 5);
+    let mut medoids: Vec<i64> = result.medoids.iter().map(|&&m| m).collect();
+    medoids.sort();
+    assert_eq!(medoids, vec![1, 101, 501]);
+    assert_eq!(result.cost, 6.0);
+}
+
+#[test]
+fn outlier_scores_match_brute_force() {
+    use outliers::{self, LocalOutlierFactor};
+
+    // Distinct pairwise distances, so neighbor sets are unambiguous.
+    let data: Vec<f64> = (0..60)
+        .map(|i| ((i * 7919) % 1000) as f64 + (i as f64).sqrt())
+        .collect();
+    let ct: CoverTree<f64> = data.iter().cloned().collect();
+    let k = 4;
+
+    let neighbors = |p: f64, skip_self: bool| {
+        let mut others: Vec<(f64, f64)> = data
+            .iter()
+            .filter(|&&q| !(skip_self && q == p))
+            .map(|&q| (q, (p - q).abs()))
+            .collect();
+        others.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        others.truncate(k);
+        others
+    };
+    let k_distance = |p: f64| neighbors(p, true)[k - 1].1;
+    let density = |p: f64, skip_self: bool| {
+        let reach: f64 = neighbors(p, skip_self)
+            .iter()
+            .map(|&(q, d)| d.max(k_distance(q)))
+            .sum();
+        1.0 / (reach / k as f64 + 1e-10)
+    };
+    let lof = |p: f64, skip_self: bool| {
+        let total: f64 = neighbors(p, skip_self)
+            .iter()
+            .map(|&(q, _)| density(q, true))
+            .sum();
+        total / k as f64 / density(p, skip_self)
+    };
+
+    for (&p, score) in outliers::knn_distance_scores(&ct, k) {
+        assert_eq!(score, k_distance(p));
+    }
+    assert_eq!(
+        outliers::knn_distance_score(&ct, 2000.0, k),
+        neighbors(2000.0, false)[k - 1].1);
+
+    let model = LocalOutlierFactor::new(&ct, k);
+    for (&p, score) in model.scores() {
+        assert!((score - lof(p, true)).abs() < 1e-9);
+    }
+    for &query in &[-50.0, 123.4, 500.0, 2000.0] {
+        assert!((model.score(query) - lof(query, false)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn kernel_density_matches_brute_force() {
+    use Kernel;
+
+    let ct: CoverTree<i64> = test_i64_other_data().into_iter().collect();
+    let n = ct.len() as f64;
+    for &kernel in &[Kernel::Gaussian, Kernel::Epanechnikov, Kernel::TopHat] {
+        for &bandwidth in &[10.0, 250.0, 4000.0] {
+            for &query in &[-500, 0, 1234, 4444, 9000] {
+                let expected = test_i64_other_data()
+                    .into_iter()
+                    .map(|p| kernel.weight((p - query).abs() as f64, bandwidth))
+                    .sum::<f64>() / n;
+
+                let exact = ct.kernel_density(query, kernel, bandwidth);
+                assert!((exact - expected).abs() < 1e-12);
+                for &tolerance in &[0.001, 0.05] {
+                    let approx = ct.kernel_density_approx(
+                        query,
+                        kernel,
+                        bandwidth,
+                        tolerance);
+                    assert!((approx - expected).abs() <= tolerance + 1e-12);
+                }
+            }
+        }
+    }
+    let empty: CoverTree<i64> = CoverTree::new();
+    assert_eq!(empty.kernel_density(0, Kernel::Gaussian, 1.0), 0.0);
+}
+
+#[test]
+fn knn_classifier_matches_brute_force_vote() {
+    use learn::{KnnClassifier, Weighting};
+
+    // Label by residue, with distinct distances so votes are unambiguous.
+    let data: Vec<(f64, u8)> = (0..60)
+        .map(|i| (((i * 7919) % 1000) as f64 + (i as f64).sqrt(), (i % 3) as u8))
+        .collect();
+    let k = 5;
+
+    for &weighting in &[Weighting::Uniform, Weighting::Distance] {
+        let classifier = KnnClassifier::from_items(
+            data.iter().cloned(),
+            k,
+            weighting);
+        assert_eq!(classifier.len(), data.len());
+
+        let brute = |query: f64, skip: Option<usize>| {
+            let mut near: Vec<(u8, f64)> = data
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| Some(i) != skip)
+                .map(|(_, &(p, label))| (label, (p - query).abs()))
+                .collect();
+            near.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+            near.truncate(k);
+            let mut totals = [0.0; 3];
+            for &(label, dist) in &near {
+                totals[label as usize] += match weighting {
+                    Weighting::Uniform  => 1.0,
+                    Weighting::Distance => 1.0 / dist,
+                };
+            }
+            let most = totals.iter().cloned().fold(0.0, f64::max);
+            near.iter().find(|n| totals[n.0 as usize] == most).unwrap().0
+        };
+
+        let queries: Vec<f64> = (0..40).map(|i| i as f64 * 26.3 - 15.0).collect();
+        let predicted = classifier.predict_batch(queries.iter().cloned());
+        for (&query, prediction) in queries.iter().zip(predicted) {
+            assert_eq!(prediction, Some(brute(query, None)));
+        }
+
+        let correct = (0..data.len())
+            .filter(|&i| brute(data[i].0, Some(i)) == data[i].1)
+            .count();
+        assert_eq!(
+            classifier.leave_one_out_accuracy(),
+            correct as f64 / data.len() as f64);
+    }
+}
+
+#[test]
+fn knn_regressor_predicts_weighted_means() {
+    use learn::{KnnRegressor, Weighting};
+
+    let data: Vec<(i64, f64)> = test_i64_data()
+        .into_iter()
+        .map(|p| (p, (p as f64) * 0.5 + 3.0))
+        .collect();
+    let uniform = KnnRegressor::from_items(
+        data.iter().cloned(),
+        1,
+        Weighting::Uniform);
+    for &(p, value) in &data {
+        assert_eq!(uniform.predict(p), Some(value));
+    }
+
+    let weighted = KnnRegressor::from_items(
+        data.iter().cloned(),
+        2,
+        Weighting::Distance);
+    // Between 100 and 103 the distance-weighted mean interpolates linearly.
+    assert_eq!(weighted.predict(101), Some(101.0 * 0.5 + 3.0));
+    assert!(weighted.leave_one_out_error() > 0.0);
+
+    let empty: KnnRegressor<i64> = KnnRegressor::new(3, Weighting::Uniform);
+    assert_eq!(empty.predict(4), None);
+}
+
+#[test]
+fn set_distances_match_brute_force() {
+    let a_data = test_i64_data();
+    let b_data = test_i64_other_data();
+    let a: CoverTree<i64> = a_data.iter().cloned().collect();
+    let b: CoverTree<i64> = b_data.iter().cloned().collect();
+
+    let nearest = |p: i64, to: &[i64]| to
+        .iter()
+        .map(|q| (p - q).abs() as f64)
+        .fold(f64::INFINITY, f64::min);
+    let directed = |from: &[i64], to: &[i64]| from
+        .iter()
+        .map(|&p| nearest(p, to))
+        .fold(0.0, f64::max);
+    let mean = |from: &[i64], to: &[i64]| from
+        .iter()
+        .map(|&p| nearest(p, to))
+        .sum::<f64>() / from.len() as f64;
+
+    assert_eq!(a.directed_hausdorff(&b), directed(&a_data, &b_data));
+    assert_eq!(b.directed_hausdorff(&a), directed(&b_data, &a_data));
+    assert_eq!(
+        a.hausdorff(&b),
+        directed(&a_data, &b_data).max(directed(&b_data, &a_data)));
+    let chamfer = mean(&a_data, &b_data) + mean(&b_data, &a_data);
+    assert!((a.chamfer(&b) - chamfer).abs() < 1e-9);
+    assert_eq!(a.hausdorff(&a), 0.0);
+
+    let empty: CoverTree<i64> = CoverTree::new();
+    assert_eq!(empty.directed_hausdorff(&a), 0.0);
+    assert_eq!(a.directed_hausdorff(&empty), f64::INFINITY);
+    assert_eq!(empty.chamfer(&empty), 0.0);
+}
+
+/// A point in the plane under the Euclidean metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Plane(f64, f64);
+
+impl ::MetricSpace for Plane {
+    fn distance(self, rhs: Self) -> f64 {
+        (self.0 - rhs.0).hypot(self.1 - rhs.1)
+    }
+}
+
+/// Returns pseudorandom points spread over a square.
+fn test_plane_data(n: usize) -> Vec<Plane> {
+    let mut state: u64 = 12345;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64 * 1000.0
+    };
+    (0..n).map(|_| Plane(next(), next())).collect()
+}
+
+#[test]
+fn intrinsic_dimension_tracks_data_dimension() {
+    let line: CoverTree<Plane> = test_plane_data(1500)
+        .into_iter()
+        .map(|p| Plane(p.0, 0.0))
+        .collect();
+    let plane: CoverTree<Plane> = test_plane_data(1500).into_iter().collect();
+
+    let line_mle = line.intrinsic_dimension_mle(10).unwrap();
+    let plane_mle = plane.intrinsic_dimension_mle(10).unwrap();
+    assert!((line_mle - 1.0).abs() < 0.25);
+    assert!((plane_mle - 2.0).abs() < 0.4);
+
+    let line_levels = line.intrinsic_dimension_levels().unwrap();
+    let plane_levels = plane.intrinsic_dimension_levels().unwrap();
+    assert!(line_levels < plane_levels);
+
+    let single: CoverTree<Plane> = vec![Plane(0.0, 0.0)].into_iter().collect();
+    assert_eq!(single.intrinsic_dimension_mle(3), None);
+    assert_eq!(single.intrinsic_dimension_levels(), None);
+}
+
+#[test]
+fn tune_span_factor_picks_cheapest_candidate() {
+    let sample = test_i64_other_data();
+    let queries: Vec<i64> = (0..40).map(|i| i * 237 - 300).collect();
+    let candidates = [1.2, 1.5, 2.0, 3.0];
+
+    let cost = |span_factor: f64| {
+        let mut tree = CoverTree::with_span_factor(span_factor);
+        tree.extend(sample.iter().cloned());
+        queries
+            .iter()
+            .map(|&q| tree.find_nearest_with_stats(q).1.distance_evaluations)
+            .sum::<usize>()
+    };
+    let lowest = candidates.iter().map(|&c| cost(c)).min().unwrap();
+
+    let best = CoverTree::tune_span_factor_among(&sample, &queries, &candidates);
+    assert_eq!(cost(best), lowest);
+    assert!(::SPAN_FACTOR_CANDIDATES
+        .contains(&CoverTree::tune_span_factor(&sample, &queries)));
+    assert_eq!(
+        CoverTree::tune_span_factor(&sample, &[]),
+        ::DEFAULT_SPAN_FACTOR);
+}
+
+#[test]
+fn rebuild_preserves_points() {
+    let data = test_i64_other_data();
+    let mut tree: CoverTree<i64> = data.iter().cloned().collect();
+    tree.remove_all(data.iter().cloned().step_by(3));
+    let mut before: Vec<i64> = tree.iter().cloned().collect();
+    before.sort();
+
+    tree.rebuild_with_span_factor(2.0);
+    assert_eq!(tree.span_factor(), 2.0);
+    let mut after: Vec<i64> = tree.iter().cloned().collect();
+    after.sort();
+    assert_eq!(after, before);
+    assert_eq!(tree.len(), before.len());
+
+    for &query in &[-500, 7, 1234, 99999] {
+        let nearest = *tree.find_nearest(query).unwrap();
+        let expected = *before.iter().min_by_key(|&&p| (p - query).abs()).unwrap();
+        assert_eq!((nearest - query).abs(), (expected - query).abs());
+    }
+}
+
+#[test]
+fn rebuild_policy_rebuilds_drifted_trees() {
+    let query_cost = |tree: &CoverTree<i64>| {
+        (0..100)
+            .map(|q| tree.find_nearest_with_stats(q * 20 + 7).1.distance_evaluations)
+            .sum::<usize>()
+    };
+    let managed = |policy: ::RebuildPolicy| {
+        let mut tree: CoverTree<i64> = CoverTree::new();
+        tree.set_rebuild_policy(Some(policy));
+        tree.extend(0..2000);
+        assert_eq!(tree.len(), 2000);
+        assert_eq!(tree.rebuild_policy(), Some(policy));
+        tree
+    };
+
+    // Sorted insertion builds a slow tree unless a policy rebuilds it.
+    let plain: CoverTree<i64> = (0..2000).collect();
+    let cost_policy = ::RebuildPolicy::every(100).with_max_cost_growth(1.2);
+    let depth_policy = ::RebuildPolicy::every(100).with_max_depth_growth(1.1);
+    assert!(query_cost(&managed(cost_policy)) < query_cost(&plain));
+    assert!(query_cost(&managed(depth_policy)) < query_cost(&plain));
+
+    // Trees that stay within the limits are never rebuilt.
+    let lax = ::RebuildPolicy::every(100)
+        .with_max_cost_growth(1000.0)
+        .with_max_depth_growth(1000.0);
+    assert_eq!(managed(lax), plain);
+
+    let mut cleared = managed(cost_policy);
+    cleared.clear();
+    assert_eq!(cleared.rebuild_policy(), Some(cost_policy));
+    cleared.set_rebuild_policy(None);
+    cleared.extend(0..2000);
+    assert_eq!(cleared, plain);
+}
+
+#[test]
+fn rebuild_policy_counts_one_change_per_removal() {
+    let counter = ::DistanceCounter::new();
+    let mut plain: CoverTree<_> = test_i64_data()
+        .into_iter()
+        .map(|p| counter.wrap(p))
+        .collect();
+    let policy = ::RebuildPolicy::every(1).with_max_depth_growth(1000.0);
+    let mut managed = plain.clone();
+    managed.set_rebuild_policy(Some(policy));
+
+    // Removing the root orphans its descendents, which are reinserted.
+    let root = *plain.root().unwrap().point();
+    counter.reset();
+    plain.remove(root);
+    let plain_cost = counter.count();
+    counter.reset();
+    managed.remove(root);
+    let managed_cost = counter.count();
+    assert_eq!(managed, plain);
+
+    // Measuring the tree once is all the policy adds.
+    counter.reset();
+    plain.set_rebuild_policy(Some(policy));
+    assert_eq!(managed_cost - plain_cost, counter.count());
+}